@@ -1,6 +1,6 @@
 use std::fmt;
 
-#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[repr(i8)]
 #[allow(dead_code)]
 pub enum Accidental {
@@ -12,6 +12,18 @@ pub enum Accidental {
 }
 
 impl Accidental {
+    pub const fn from_interval(interval: i8) -> Option<Self> {
+        use Accidental::*;
+        match interval {
+            -2 => Some(DoubleFlat),
+            -1 => Some(Flat),
+            0 => Some(Natural),
+            1 => Some(Sharp),
+            2 => Some(DoubleSharp),
+            _ => None,
+        }
+    }
+
     pub const fn interval(self) -> i8 {
         self as i8
     }
@@ -0,0 +1,412 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::audio::synth::Voice;
+use crate::audio::wav::SampleFormat;
+use crate::chord::voicing;
+use crate::interval::Interval;
+use crate::note::{Note, UnspellableNote};
+use crate::pitch::Pitch;
+use crate::tuning::{EqualTemperament, Tuning};
+
+#[cfg(feature = "playback")]
+pub mod device;
+pub mod sink;
+pub mod synth;
+pub mod wav;
+
+/// Mono audio, as samples between -1 and 1.
+#[derive(fmt::Debug, Clone, PartialEq)]
+pub struct Audio {
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+impl Audio {
+    pub fn silence(sample_rate: u32, len: usize) -> Self {
+        Audio {
+            sample_rate,
+            samples: vec![0.0; len],
+        }
+    }
+
+    /// The length in seconds.
+    pub fn duration(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+
+    /// Adds `samples` in, starting at sample `offset`, lengthening the audio
+    /// if they run past its end.
+    pub fn mix(&mut self, samples: &[f32], offset: usize) {
+        if self.samples.len() < offset + samples.len() {
+            self.samples.resize(offset + samples.len(), 0.0);
+        }
+        for (mixed, sample) in self.samples[offset..].iter_mut().zip(samples) {
+            *mixed += sample;
+        }
+    }
+
+    /// The audio at another sample rate, interpolating linearly between
+    /// samples.
+    pub fn resampled(&self, sample_rate: u32) -> Self {
+        if sample_rate == self.sample_rate || self.samples.is_empty() {
+            return Audio {
+                sample_rate,
+                samples: self.samples.clone(),
+            };
+        }
+
+        let step = self.sample_rate as f64 / sample_rate as f64;
+        let len = (self.samples.len() as f64 / step).round() as usize;
+        let last = self.samples.len() - 1;
+        let samples = (0..len)
+            .map(|n| {
+                let position = n as f64 * step;
+                let i = (position as usize).min(last);
+                let fraction = (position - i as f64) as f32;
+                let next = self.samples[(i + 1).min(last)];
+                self.samples[i] + (next - self.samples[i]) * fraction
+            })
+            .collect();
+        Audio {
+            sample_rate,
+            samples,
+        }
+    }
+
+    pub fn write_wav(&self, writer: &mut impl Write, format: SampleFormat) -> io::Result<()> {
+        wav::write(writer, self, format)
+    }
+
+    pub fn save_wav(&self, path: impl AsRef<Path>, format: SampleFormat) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_wav(&mut writer, format)?;
+        writer.flush()
+    }
+}
+
+/// How loud a note is over time, with times in seconds and `sustain` a level
+/// between 0 and 1.
+#[derive(fmt::Debug, Copy, Clone, PartialEq)]
+pub struct Envelope {
+    pub attack: f64,
+    pub decay: f64,
+    pub sustain: f64,
+    /// How long the note takes to fade out once it is let go.
+    pub release: f64,
+}
+
+impl Envelope {
+    /// The gain `time` seconds into a note that is held for `held` seconds.
+    pub fn gain(&self, time: f64, held: f64) -> f64 {
+        if time >= held {
+            let remaining = 1.0 - (time - held) / self.release;
+            self.held_level(held) * remaining.max(0.0)
+        } else {
+            self.held_level(time)
+        }
+    }
+
+    // The gain `time` seconds into a note that is still held.
+    fn held_level(&self, time: f64) -> f64 {
+        if time < self.attack {
+            time / self.attack
+        } else if time < self.attack + self.decay {
+            1.0 - (1.0 - self.sustain) * (time - self.attack) / self.decay
+        } else {
+            self.sustain
+        }
+    }
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Envelope {
+            attack: 0.01,
+            decay: 0.1,
+            sustain: 0.7,
+            release: 0.3,
+        }
+    }
+}
+
+/// How to play the two notes of an interval.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IntervalStyle {
+    Ascending,
+    Descending,
+    Harmonic,
+}
+
+/// Renders pitches as audio. Each note or chord is held for one beat at
+/// `tempo` beats per minute, and is followed by `gap` seconds of silence
+/// before the next, while it fades out over the envelope's release. Pitches
+/// the tuning has no frequency for are silent.
+pub struct Renderer {
+    pub sample_rate: u32,
+    pub voice: Voice,
+    pub envelope: Envelope,
+    pub tempo: f64,
+    pub gap: f64,
+    /// The peak level of a single note, or of all the notes of a chord
+    /// together.
+    pub volume: f64,
+    pub tuning: Box<dyn Tuning>,
+}
+
+impl Renderer {
+    fn beat(&self) -> f64 {
+        60.0 / self.tempo
+    }
+
+    fn samples(&self, seconds: f64) -> usize {
+        (seconds * self.sample_rate as f64).round() as usize
+    }
+
+    /// Plays each group of pitches together, one group after another.
+    pub fn render(&self, events: &[Vec<Pitch>]) -> Audio {
+        let held = self.beat();
+        let len = self.samples(held + self.envelope.release);
+
+        let mut audio = Audio::silence(self.sample_rate, 0);
+        for (i, pitches) in events.iter().enumerate() {
+            let offset = self.samples(i as f64 * (held + self.gap));
+            for &pitch in pitches {
+                let frequency = self.tuning.frequency(pitch);
+                if !frequency.is_finite() {
+                    continue;
+                }
+
+                let level = self.volume / pitches.len() as f64;
+                let mut samples = self.voice.render(frequency, self.sample_rate, len);
+                for (n, sample) in samples.iter_mut().enumerate() {
+                    let time = n as f64 / self.sample_rate as f64;
+                    *sample *= (level * self.envelope.gain(time, held)) as f32;
+                }
+                audio.mix(&samples, offset);
+            }
+        }
+
+        let end = self.samples((events.len() as f64 * (held + self.gap) - self.gap).max(0.0));
+        if audio.samples.len() < end + self.samples(self.envelope.release) {
+            audio
+                .samples
+                .resize(end + self.samples(self.envelope.release), 0.0);
+        }
+        audio
+    }
+
+    pub fn render_melody(&self, pitches: &[Pitch]) -> Audio {
+        let events: Vec<Vec<Pitch>> = pitches.iter().map(|&pitch| vec![pitch]).collect();
+        self.render(&events)
+    }
+
+    pub fn render_chord(&self, pitches: &[Pitch]) -> Audio {
+        self.render(&[pitches.to_vec()])
+    }
+
+    /// Plays `interval` above `low`, or below it if descending.
+    pub fn render_interval(
+        &self,
+        low: Pitch,
+        interval: Interval,
+        style: IntervalStyle,
+    ) -> Result<Audio, UnspellableNote> {
+        Ok(self.render(&interval_events(low, interval, style)?))
+    }
+
+    /// Plays the notes of a chord, such as those from `ChordQuality::spell`,
+    /// stacked upwards from `low`.
+    pub fn render_spelled_chord(&self, low: Pitch, notes: &[Note]) -> Audio {
+        self.render_chord(&ascending(low, notes))
+    }
+
+    /// Plays the notes of a scale, such as those from `Scale::spell`, upwards
+    /// from `low`, ending on the first note an octave higher.
+    pub fn render_scale(&self, low: Pitch, notes: &[Note]) -> Audio {
+        let mut notes = notes.to_vec();
+        notes.extend(notes.first().copied());
+        self.render_melody(&ascending(low, &notes))
+    }
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer {
+            sample_rate: 44100,
+            voice: Voice::default(),
+            envelope: Envelope::default(),
+            tempo: 90.0,
+            gap: 0.1,
+            volume: 0.8,
+            tuning: Box::new(EqualTemperament::default()),
+        }
+    }
+}
+
+/// The pitches of `interval` above `low`, or below it if descending, grouped
+/// as `Renderer::render` plays them.
+pub fn interval_events(
+    low: Pitch,
+    interval: Interval,
+    style: IntervalStyle,
+) -> Result<Vec<Vec<Pitch>>, UnspellableNote> {
+    Ok(match style {
+        IntervalStyle::Ascending => vec![vec![low], vec![low.transpose(interval)?]],
+        IntervalStyle::Descending => vec![vec![low], vec![low.transpose_down(interval)?]],
+        IntervalStyle::Harmonic => vec![vec![low, low.transpose(interval)?]],
+    })
+}
+
+/// Places each note above the one before it, starting from the first at or
+/// above `low`.
+pub fn ascending(low: Pitch, notes: &[Note]) -> Vec<Pitch> {
+    let mut floor = Pitch::from_semitones(low.semitones() - 1);
+    notes
+        .iter()
+        .map(|&note| {
+            floor = voicing::above(note, floor);
+            floor
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chord::quality::sevenths;
+    use crate::scale::catalogue;
+
+    fn pitch(s: &str) -> Pitch {
+        s.parse().unwrap()
+    }
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    fn renderer() -> Renderer {
+        Renderer {
+            sample_rate: 8000,
+            tempo: 120.0,
+            gap: 0.25,
+            ..Renderer::default()
+        }
+    }
+
+    // Upward zero crossings between `start` and `end` seconds.
+    fn crossings(audio: &Audio, start: f64, end: f64) -> usize {
+        let range =
+            (start * audio.sample_rate as f64) as usize..(end * audio.sample_rate as f64) as usize;
+        audio.samples[range]
+            .windows(2)
+            .filter(|pair| pair[0] <= 0.0 && pair[1] > 0.0)
+            .count()
+    }
+
+    #[test]
+    fn test_ascending() {
+        let g7 = sevenths::DOMINANT.spell(note("G"));
+        let pitches: Vec<String> = ascending(pitch("G3"), &g7)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(pitches, ["G3", "B3", "D4", "F4"]);
+
+        let pitches = ascending(pitch("D4"), &[note("C"), note("C"), note("B")]);
+        assert_eq!(pitches, [pitch("C5"), pitch("C6"), pitch("B6")]);
+    }
+
+    #[test]
+    fn test_render() {
+        let renderer = renderer();
+
+        let melody = renderer.render_melody(&[pitch("A4"), pitch("A5")]);
+        // Two half-second beats, a quarter-second gap and the release.
+        assert_close(melody.duration(), 1.55);
+        assert!((131..=133).contains(&crossings(&melody, 0.1, 0.4)));
+        assert!((263..=265).contains(&crossings(&melody, 0.85, 1.15)));
+        assert!(melody.samples[..4000].iter().all(|s| s.abs() <= 0.8));
+        assert_eq!(melody, renderer.render_melody(&[pitch("A4"), pitch("A5")]));
+
+        let fifth = renderer
+            .render_interval(pitch("C4"), "P5".parse().unwrap(), IntervalStyle::Harmonic)
+            .unwrap();
+        assert_eq!(fifth.samples.len(), 8000 * 8 / 10);
+        assert!(fifth.samples.iter().all(|s| s.abs() <= 0.8));
+
+        let descending = renderer
+            .render_interval(
+                pitch("C4"),
+                "M3".parse().unwrap(),
+                IntervalStyle::Descending,
+            )
+            .unwrap();
+        assert_eq!(
+            descending,
+            renderer.render_melody(&[pitch("C4"), pitch("Ab3")])
+        );
+
+        let major = catalogue::find("Major").unwrap();
+        let scale = renderer.render_scale(pitch("C4"), &major.spell(note("C")));
+        assert_close(scale.duration(), 8.0 * 0.75 - 0.25 + 0.3);
+
+        let chord = renderer.render_spelled_chord(pitch("C3"), &sevenths::MAJOR.spell(note("C")));
+        assert_eq!(
+            chord,
+            renderer.render_chord(&[pitch("C3"), pitch("E3"), pitch("G3"), pitch("B3")])
+        );
+        assert!(renderer.render(&[]).samples.len() <= 8000 * 3 / 10);
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.001,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_resampled() {
+        let audio = Audio {
+            sample_rate: 4,
+            samples: vec![0.0, 1.0, 0.0, -1.0],
+        };
+        assert_eq!(
+            audio.resampled(8).samples,
+            [0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -1.0]
+        );
+        assert_eq!(audio.resampled(2).samples, [0.0, 0.0]);
+        assert_eq!(audio.resampled(4), audio);
+
+        let renderer = renderer();
+        let melody = renderer.render_melody(&[pitch("A4")]);
+        let resampled = melody.resampled(44100);
+        assert_close(resampled.duration(), melody.duration());
+        assert_eq!(
+            crossings(&resampled, 0.1, 0.4),
+            crossings(&melody, 0.1, 0.4)
+        );
+    }
+
+    #[test]
+    fn test_envelope() {
+        let envelope = Envelope::default();
+        assert_close(envelope.gain(0.0, 1.0), 0.0);
+        assert_close(envelope.gain(0.005, 1.0), 0.5);
+        assert_close(envelope.gain(0.01, 1.0), 1.0);
+        assert_close(envelope.gain(0.06, 1.0), 0.85);
+        assert_close(envelope.gain(0.5, 1.0), 0.7);
+        assert_close(envelope.gain(1.15, 1.0), 0.35);
+        assert_close(envelope.gain(2.0, 1.0), 0.0);
+        // Let go during the attack, the note fades from where it got to.
+        assert_close(envelope.gain(0.005 + 0.15, 0.005), 0.25);
+        // Long notes too.
+        assert_close(envelope.gain(2.51, 2.5), 0.7 * (1.0 - 0.01 / 0.3));
+        assert_close(envelope.gain(100.0, 60.0), 0.0);
+    }
+}
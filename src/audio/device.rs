@@ -0,0 +1,91 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+
+use crate::audio::sink::{AudioSink, SinkError};
+use crate::audio::Audio;
+
+// How long to wait past the end of the audio before giving up on the
+// device, and how long to let its last buffer drain.
+const TIMEOUT: Duration = Duration::from_secs(2);
+const DRAIN: Duration = Duration::from_millis(100);
+
+/// Plays audio on the default output device, in every channel.
+pub struct DeviceSink {
+    device: cpal::Device,
+    config: cpal::SupportedStreamConfig,
+}
+
+impl DeviceSink {
+    pub fn new() -> Result<Self, SinkError> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| SinkError::Device("no output device".to_string()))?;
+        let config = device
+            .default_output_config()
+            .map_err(|err| SinkError::Device(err.to_string()))?;
+        Ok(DeviceSink { device, config })
+    }
+
+    fn play_as<T>(&self, audio: Audio) -> Result<(), SinkError>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let config = self.config.config();
+        let channels = config.channels as usize;
+        let duration = Duration::from_secs_f64(audio.duration());
+        let samples = audio.samples;
+        let mut position = 0;
+
+        let (sender, receiver) = mpsc::channel();
+        let errors = sender.clone();
+        let stream = self
+            .device
+            .build_output_stream(
+                &config,
+                move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                    for frame in data.chunks_mut(channels) {
+                        let sample = samples.get(position).copied().unwrap_or(0.0);
+                        frame.fill(T::from_sample(sample));
+                        position += 1;
+                    }
+                    if position >= samples.len() {
+                        let _ = sender.send(Ok(()));
+                    }
+                },
+                move |err| {
+                    let _ = errors.send(Err(err.to_string()));
+                },
+                None,
+            )
+            .map_err(|err| SinkError::Device(err.to_string()))?;
+        stream
+            .play()
+            .map_err(|err| SinkError::Device(err.to_string()))?;
+
+        match receiver.recv_timeout(duration + TIMEOUT) {
+            Ok(result) => result.map_err(SinkError::Device)?,
+            Err(_) => return Err(SinkError::Device("playback timed out".to_string())),
+        }
+        thread::sleep(DRAIN);
+        Ok(())
+    }
+}
+
+impl AudioSink for DeviceSink {
+    fn play(&mut self, audio: &Audio) -> Result<(), SinkError> {
+        let audio = audio.resampled(self.config.sample_rate().0);
+        match self.config.sample_format() {
+            cpal::SampleFormat::F32 => self.play_as::<f32>(audio),
+            cpal::SampleFormat::I16 => self.play_as::<i16>(audio),
+            cpal::SampleFormat::U16 => self.play_as::<u16>(audio),
+            format => Err(SinkError::Device(format!(
+                "unsupported sample format {}",
+                format
+            ))),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+use std::{error, fmt, fs, io};
+
+use crate::audio::wav::SampleFormat;
+use crate::audio::Audio;
+
+/// Somewhere to play audio.
+pub trait AudioSink {
+    /// Plays `audio`, returning once it has finished.
+    fn play(&mut self, audio: &Audio) -> Result<(), SinkError>;
+}
+
+#[derive(fmt::Debug)]
+pub enum SinkError {
+    Io(io::Error),
+    /// The sound device couldn't be opened or failed while playing.
+    Device(String),
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SinkError::Io(err) => write!(f, "{}", err),
+            SinkError::Device(message) => write!(f, "sound device error: {}", message),
+        }
+    }
+}
+
+impl error::Error for SinkError {}
+
+impl From<io::Error> for SinkError {
+    fn from(err: io::Error) -> Self {
+        SinkError::Io(err)
+    }
+}
+
+/// Plays nothing, but keeps everything it was given.
+#[derive(fmt::Debug, Clone, Default)]
+pub struct NullSink {
+    pub played: Vec<Audio>,
+}
+
+impl NullSink {
+    pub fn new() -> Self {
+        NullSink::default()
+    }
+}
+
+impl AudioSink for NullSink {
+    fn play(&mut self, audio: &Audio) -> Result<(), SinkError> {
+        self.played.push(audio.clone());
+        Ok(())
+    }
+}
+
+/// Writes each piece of audio to its own numbered WAV file in a directory,
+/// as `0001.wav`, `0002.wav` and so on.
+#[derive(fmt::Debug, Clone)]
+pub struct WavSink {
+    directory: PathBuf,
+    format: SampleFormat,
+    written: Vec<PathBuf>,
+}
+
+impl WavSink {
+    /// Creates `directory` if it doesn't exist.
+    pub fn new(directory: impl AsRef<Path>, format: SampleFormat) -> io::Result<Self> {
+        fs::create_dir_all(&directory)?;
+        Ok(WavSink {
+            directory: directory.as_ref().to_path_buf(),
+            format,
+            written: Vec::new(),
+        })
+    }
+
+    /// The files written so far, in order.
+    pub fn written(&self) -> &[PathBuf] {
+        &self.written
+    }
+}
+
+impl AudioSink for WavSink {
+    fn play(&mut self, audio: &Audio) -> Result<(), SinkError> {
+        let path = self
+            .directory
+            .join(format!("{:04}.wav", self.written.len() + 1));
+        audio.save_wav(&path, self.format)?;
+        self.written.push(path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::Renderer;
+
+    #[test]
+    fn test_sinks() {
+        let renderer = Renderer {
+            sample_rate: 8000,
+            ..Renderer::default()
+        };
+        let prompts = [
+            renderer.render_melody(&["C4".parse().unwrap()]),
+            renderer.render_chord(&["C4".parse().unwrap(), "G4".parse().unwrap()]),
+        ];
+
+        let mut null = NullSink::new();
+        for audio in &prompts {
+            null.play(audio).unwrap();
+        }
+        assert_eq!(null.played, prompts);
+
+        let directory = std::env::temp_dir().join(format!("wav_sink_{}", std::process::id()));
+        let mut wav = WavSink::new(&directory, SampleFormat::Int16).unwrap();
+        for audio in &prompts {
+            wav.play(audio).unwrap();
+        }
+        assert_eq!(
+            wav.written(),
+            [directory.join("0001.wav"), directory.join("0002.wav")]
+        );
+        for (path, audio) in wav.written().iter().zip(&prompts) {
+            let mut expected = Vec::new();
+            audio.write_wav(&mut expected, SampleFormat::Int16).unwrap();
+            assert_eq!(fs::read(path).unwrap(), expected);
+        }
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
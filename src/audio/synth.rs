@@ -0,0 +1,172 @@
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+/// A simple synthesiser voice. Every voice is deterministic: a note at the
+/// same frequency always renders the same samples.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Voice {
+    #[default]
+    Sine,
+    /// Decaying harmonics, the higher ones dying away sooner, loosely like a
+    /// piano.
+    Additive,
+    /// A Karplus–Strong plucked string, excited by noise from a fixed seed.
+    Plucked,
+}
+
+// The harmonics of the additive voice: their levels, and how fast the
+// fundamental decays per second. Each harmonic decays that much faster for
+// its number.
+const HARMONICS: [f64; 8] = [1.0, 0.5, 0.35, 0.25, 0.15, 0.1, 0.05, 0.03];
+const DECAY: f64 = 1.2;
+
+// How much the plucked string's energy is kept each time round the loop.
+const DAMPING: f64 = 0.996;
+const SEED: u32 = 0x5eed;
+
+impl Voice {
+    /// `len` samples of the voice sounding at `frequency`, between -1 and 1
+    /// and before any envelope.
+    pub fn render(self, frequency: f64, sample_rate: u32, len: usize) -> Vec<f32> {
+        let rate = sample_rate as f64;
+        match self {
+            Voice::Sine => (0..len)
+                .map(|n| (2.0 * PI * frequency * n as f64 / rate).sin() as f32)
+                .collect(),
+            Voice::Additive => {
+                let nyquist = rate / 2.0;
+                let total: f64 = HARMONICS.iter().sum();
+                (0..len)
+                    .map(|n| {
+                        let time = n as f64 / rate;
+                        let sample: f64 = HARMONICS
+                            .iter()
+                            .enumerate()
+                            .map(|(i, level)| (i as f64 + 1.0, level))
+                            .filter(|&(harmonic, _)| harmonic * frequency < nyquist)
+                            .map(|(harmonic, level)| {
+                                level
+                                    * (-DECAY * harmonic * time).exp()
+                                    * (2.0 * PI * harmonic * frequency * time).sin()
+                            })
+                            .sum();
+                        (sample / total) as f32
+                    })
+                    .collect()
+            }
+            Voice::Plucked => {
+                let period = ((rate / frequency).round() as usize).max(2);
+                let mut random = Lcg(SEED);
+                let mut buffer: Vec<f64> = (0..period).map(|_| random.next_sample()).collect();
+
+                (0..len)
+                    .map(|n| {
+                        let i = n % period;
+                        let sample = buffer[i];
+                        buffer[i] = DAMPING * 0.5 * (sample + buffer[(i + 1) % period]);
+                        sample as f32
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Voice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Voice::Sine => "sine",
+            Voice::Additive => "additive",
+            Voice::Plucked => "plucked",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct UnrecognizedVoice(String);
+
+impl fmt::Display for UnrecognizedVoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unrecognized voice {:?} (expected sine, additive or plucked)",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Voice {
+    type Err = UnrecognizedVoice;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sine" => Ok(Voice::Sine),
+            "additive" => Ok(Voice::Additive),
+            "plucked" => Ok(Voice::Plucked),
+            _ => Err(UnrecognizedVoice(s.to_string())),
+        }
+    }
+}
+
+// A linear congruential generator, with the constants from Numerical Recipes.
+struct Lcg(u32);
+
+impl Lcg {
+    // Between -1 and 1.
+    fn next_sample(&mut self) -> f64 {
+        self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        self.0 as f64 / u32::MAX as f64 * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+
+    const VOICES: [Voice; 3] = [Voice::Sine, Voice::Additive, Voice::Plucked];
+
+    #[test]
+    fn test_voices() {
+        for &voice in &VOICES {
+            let samples = voice.render(220.0, 8000, 8000);
+            assert_eq!(samples.len(), 8000);
+            assert!(samples.iter().all(|s| s.abs() <= 1.0));
+            assert_eq!(samples, voice.render(220.0, 8000, 8000));
+        }
+
+        let sine = Voice::Sine.render(1000.0, 8000, 4);
+        let expected = [0.0, FRAC_1_SQRT_2, 1.0, FRAC_1_SQRT_2];
+        for (actual, expected) in sine.iter().zip(&expected) {
+            assert!((actual - expected).abs() < 0.0001);
+        }
+
+        // The additive voice and the plucked string both die away.
+        for &voice in &[Voice::Additive, Voice::Plucked] {
+            let samples = voice.render(220.0, 8000, 16000);
+            let peak = |range: &[f32]| range.iter().fold(0f32, |peak, s| peak.max(s.abs()));
+            assert!(peak(&samples[12000..]) < peak(&samples[..4000]) / 2.0);
+        }
+    }
+
+    #[test]
+    fn test_plucked_string() {
+        // The string repeats, a little softer, every period.
+        let samples = Voice::Plucked.render(200.0, 8000, 200);
+        let correlation: f32 = samples[..40]
+            .iter()
+            .zip(&samples[40..80])
+            .map(|(a, b)| a * b)
+            .sum();
+        assert!(correlation > 0.0);
+
+        // The first period is the noise that excites the string.
+        let mut random = Lcg(SEED);
+        for &sample in &samples[..40] {
+            assert_eq!(sample, random.next_sample() as f32);
+        }
+    }
+}
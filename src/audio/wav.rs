@@ -0,0 +1,119 @@
+use std::io::{self, Write};
+
+use crate::audio::Audio;
+
+/// How samples are stored in a WAV file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SampleFormat {
+    /// 16-bit integer PCM. Samples beyond -1 and 1 are clipped.
+    Int16,
+    /// 32-bit IEEE float.
+    Float32,
+}
+
+impl SampleFormat {
+    const fn bytes(self) -> u32 {
+        match self {
+            SampleFormat::Int16 => 2,
+            SampleFormat::Float32 => 4,
+        }
+    }
+}
+
+const PCM: u16 = 1;
+const IEEE_FLOAT: u16 = 3;
+
+/// Writes `audio` as a mono WAV file. Float files carry the extended format
+/// chunk and the `fact` chunk that the format requires for them.
+pub fn write(writer: &mut impl Write, audio: &Audio, format: SampleFormat) -> io::Result<()> {
+    let bytes = format.bytes();
+    let data_len = audio.samples.len() as u32 * bytes;
+    let (tag, format_len, fact_len) = match format {
+        SampleFormat::Int16 => (PCM, 16, 0),
+        SampleFormat::Float32 => (IEEE_FLOAT, 18, 12),
+    };
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(4 + 8 + format_len + fact_len + 8 + data_len).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&format_len.to_le_bytes())?;
+    writer.write_all(&tag.to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?;
+    writer.write_all(&audio.sample_rate.to_le_bytes())?;
+    writer.write_all(&(audio.sample_rate * bytes).to_le_bytes())?;
+    writer.write_all(&(bytes as u16).to_le_bytes())?;
+    writer.write_all(&(8 * bytes as u16).to_le_bytes())?;
+    if format == SampleFormat::Float32 {
+        writer.write_all(&0u16.to_le_bytes())?;
+        writer.write_all(b"fact")?;
+        writer.write_all(&4u32.to_le_bytes())?;
+        writer.write_all(&(audio.samples.len() as u32).to_le_bytes())?;
+    }
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_len.to_le_bytes())?;
+    for &sample in &audio.samples {
+        match format {
+            SampleFormat::Int16 => {
+                let sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+                writer.write_all(&sample.to_le_bytes())?;
+            }
+            SampleFormat::Float32 => writer.write_all(&sample.to_le_bytes())?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audio() -> Audio {
+        Audio {
+            sample_rate: 8000,
+            samples: vec![0.0, 0.5, -1.0, 2.0],
+        }
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+    }
+
+    #[test]
+    fn test_int16() {
+        let mut bytes = Vec::new();
+        write(&mut bytes, &audio(), SampleFormat::Int16).unwrap();
+
+        assert_eq!(bytes.len(), 44 + 8);
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(u32_at(&bytes, 4), 44 + 8 - 8);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(
+            bytes[16..36],
+            [16, 0, 0, 0, 1, 0, 1, 0, 0x40, 0x1f, 0, 0, 0x80, 0x3e, 0, 0, 2, 0, 16, 0]
+        );
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(u32_at(&bytes, 40), 8);
+        assert_eq!(bytes[44..], [0, 0, 0x00, 0x40, 0x01, 0x80, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn test_float32() {
+        let mut bytes = Vec::new();
+        write(&mut bytes, &audio(), SampleFormat::Float32).unwrap();
+
+        assert_eq!(bytes.len(), 58 + 16);
+        assert_eq!(u32_at(&bytes, 4), 58 + 16 - 8);
+        assert_eq!(u32_at(&bytes, 16), 18);
+        assert_eq!(bytes[20..22], [3, 0]);
+        assert_eq!(u32_at(&bytes, 28), 32000);
+        assert_eq!(bytes[32..38], [4, 0, 32, 0, 0, 0]);
+        assert_eq!(&bytes[38..42], b"fact");
+        assert_eq!(u32_at(&bytes, 46), 4);
+        assert_eq!(&bytes[50..54], b"data");
+        assert_eq!(u32_at(&bytes, 54), 16);
+        assert_eq!(bytes[58 + 12..], 2f32.to_le_bytes());
+    }
+}
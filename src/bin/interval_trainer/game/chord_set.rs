@@ -0,0 +1,77 @@
+use clap::arg_enum;
+use music_exercises::chord::quality::{
+    added,
+    altered,
+    elevenths,
+    ninths,
+    sevenths,
+    sixths,
+    thirteenths,
+    triads,
+    ChordQuality,
+};
+use music_exercises::chord::registry::DEFINITIONS;
+
+arg_enum! {
+    /// Which chord qualities the chords rounds draw from.
+    #[derive(Debug, Copy, Clone)]
+    pub enum ChordSet {
+        Standard,
+        Extended,
+        Altered,
+        All,
+    }
+}
+
+impl Default for ChordSet {
+    fn default() -> Self {
+        ChordSet::Standard
+    }
+}
+
+impl ChordSet {
+    pub fn qualities(self) -> Vec<ChordQuality> {
+        let chords: Vec<&ChordQuality> = match self {
+            ChordSet::Standard => vec![
+                &triads::MAJOR,
+                &triads::MINOR,
+                &triads::DIMINISHED,
+                &triads::AUGMENTED,
+                &sevenths::MAJOR,
+                &sevenths::MINOR,
+                &sevenths::DIMINISHED,
+                &sevenths::HALF_DIMINISHED,
+                &sevenths::DOMINANT,
+            ],
+            ChordSet::Extended => vec![
+                &sixths::MAJOR,
+                &sixths::MINOR,
+                &sixths::SIX_NINE,
+                &added::NINTH,
+                &sevenths::SUSPENDED_4,
+                &ninths::DOMINANT,
+                &ninths::MAJOR,
+                &ninths::MINOR,
+                &ninths::SUSPENDED_4,
+                &elevenths::MINOR,
+                &thirteenths::DOMINANT,
+                &thirteenths::MAJOR,
+            ],
+            ChordSet::Altered => vec![
+                &altered::FLAT_9,
+                &altered::SHARP_9,
+                &altered::SHARP_11,
+                &altered::FLAT_13,
+                &altered::ALTERED,
+            ],
+            ChordSet::All => {
+                return DEFINITIONS
+                    .iter()
+                    .map(|definition| definition.quality())
+                    .collect()
+            }
+        };
+
+        chords.into_iter().cloned().collect()
+    }
+}
@@ -1,10 +1,15 @@
-use music_exercises::chord::quality::ChordQuality;
+// The option enums implement `Default` by hand, since `arg_enum!` doesn't
+// accept a `#[default]` variant attribute.
+#![allow(clippy::derivable_impls)]
+
 use music_exercises::interval::canonical::CanonicalInterval;
 use music_exercises::note::Note;
-use music_exercises::scale::Scale;
+use music_exercises::scale::{catalogue, Scale};
 use once_cell::sync::Lazy;
 
+pub mod chord_set;
 pub mod mode;
+pub mod player;
 pub mod round;
 pub mod scorekeeper;
 
@@ -23,12 +28,9 @@ const STANDARD_NOTES: [Note; 17] = {
         result[i] = note;
         i += 1;
 
-        match note.accidental() {
-            Sharp => {
-                result[i] = Note(note.note_name().step_up(), Flat);
-                i += 1;
-            }
-            _ => {}
+        if let Sharp = note.accidental() {
+            result[i] = Note(note.note_name().step_up(), Flat);
+            i += 1;
         }
 
         pitch += 1;
@@ -53,26 +55,9 @@ const STANDARD_INTERVALS: [CanonicalInterval; 11] = {
     ]
 };
 
-static STANDARD_CHORD_QUALITIES: Lazy<Vec<ChordQuality>> = Lazy::new(|| {
-    use music_exercises::chord::quality::{sevenths, triads};
-
-    vec![
-        triads::MAJOR.clone(),
-        triads::MINOR.clone(),
-        triads::DIMINISHED.clone(),
-        triads::AUGMENTED.clone(),
-        sevenths::MAJOR.clone(),
-        sevenths::MINOR.clone(),
-        sevenths::DIMINISHED.clone(),
-        sevenths::HALF_DIMINISHED.clone(),
-        sevenths::DOMINANT.clone(),
-    ]
-});
-
 static STANDARD_SCALES: Lazy<Vec<Scale>> = Lazy::new(|| {
-    vec![
-        Scale::from_intervals("Major", &[2, 2, 1, 2, 2, 2, 1]),
-        Scale::from_intervals("Minor", &[2, 1, 2, 2, 1, 2, 2]),
-        Scale::from_intervals("Harmonic Minor", &[2, 1, 2, 2, 1, 3, 1]),
-    ]
+    ["Major", "Natural Minor", "Harmonic Minor", "Melodic Minor"]
+        .iter()
+        .map(|name| catalogue::find(name).unwrap())
+        .collect()
 });
@@ -1,7 +1,10 @@
 use clap::arg_enum;
+use music_exercises::tuning::scala::ScalaTuning;
 use rand::seq::SliceRandom;
 use rand::Rng;
 
+use crate::game::chord_set::ChordSet;
+use crate::game::player::Player;
 use crate::game::round::{ChordsRound, IntervalsRound, Round, ScalesRound};
 use crate::game::scorekeeper::Scorekeeper;
 
@@ -22,17 +25,24 @@ impl Default for GameMode {
 }
 
 impl GameMode {
-    pub fn play_round(&self, rng: &mut impl Rng, scorekeeper: &mut Scorekeeper) {
+    pub fn play_round(
+        &self,
+        rng: &mut impl Rng,
+        chords: ChordSet,
+        scorekeeper: &mut Scorekeeper,
+        tuning: Option<&ScalaTuning>,
+        player: Option<&mut Player>,
+    ) {
         match *self {
             GameMode::Mixed => {
                 let round_mode = [GameMode::Intervals, GameMode::Chords, GameMode::Scales]
                     .choose(rng)
                     .unwrap();
-                round_mode.play_round(rng, scorekeeper);
+                round_mode.play_round(rng, chords, scorekeeper, tuning, player);
             }
-            GameMode::Intervals => IntervalsRound::new(rng).play(scorekeeper),
-            GameMode::Chords => ChordsRound::new(rng).play(scorekeeper),
-            GameMode::Scales => ScalesRound::new(rng).play(scorekeeper),
+            GameMode::Intervals => IntervalsRound::new(rng, tuning).play(scorekeeper, player),
+            GameMode::Chords => ChordsRound::new(rng, chords).play(scorekeeper, player),
+            GameMode::Scales => ScalesRound::new(rng).play(scorekeeper, player),
         }
     }
 }
@@ -0,0 +1,41 @@
+use clap::arg_enum;
+use music_exercises::audio::sink::AudioSink;
+use music_exercises::audio::Renderer;
+use music_exercises::pitch::Pitch;
+
+arg_enum! {
+    /// Where the prompts are played.
+    #[derive(Debug, Copy, Clone)]
+    pub enum AudioOutput {
+        Off,
+        Device,
+        Wav,
+    }
+}
+
+impl Default for AudioOutput {
+    fn default() -> Self {
+        AudioOutput::Off
+    }
+}
+
+/// Renders each round's prompt and sends it to a sink.
+pub struct Player {
+    renderer: Renderer,
+    sink: Box<dyn AudioSink>,
+}
+
+impl Player {
+    pub fn new(renderer: Renderer, sink: Box<dyn AudioSink>) -> Self {
+        Player { renderer, sink }
+    }
+
+    /// Plays each group of pitches together, one group after another. A
+    /// failure is reported but doesn't stop the game.
+    pub fn play(&mut self, events: &[Vec<Pitch>]) {
+        let audio = self.renderer.render(events);
+        if let Err(e) = self.sink.play(&audio) {
+            eprintln!("  Couldn't play the prompt: {}.", e);
+        }
+    }
+}
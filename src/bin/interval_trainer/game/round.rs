@@ -4,15 +4,23 @@ use std::io::prelude::*;
 use std::time::Duration;
 
 use either::*;
-use music_exercises::chord::quality::ChordQuality;
+use music_exercises::audio::{self, IntervalStyle};
+use music_exercises::chord::symbol::ChordSymbol;
 use music_exercises::interval::canonical::CanonicalInterval;
-use music_exercises::note::{Note, UnrecognizedNote};
+use music_exercises::interval::Interval;
+use music_exercises::note::parse::ParseNoteError;
+use music_exercises::note::{Note, UnspellableNote};
+use music_exercises::pitch::Pitch;
 use music_exercises::scale::modal::ModalScale;
 use music_exercises::scale::mode::Mode;
 use music_exercises::scale::Scale;
+use music_exercises::tuning;
+use music_exercises::tuning::scala::ScalaTuning;
 use rand::prelude::*;
 
-use super::{STANDARD_CHORD_QUALITIES, STANDARD_INTERVALS, STANDARD_NOTES, STANDARD_SCALES};
+use super::{STANDARD_INTERVALS, STANDARD_NOTES, STANDARD_SCALES};
+use crate::game::chord_set::ChordSet;
+use crate::game::player::Player;
 use crate::game::scorekeeper::Scorekeeper;
 
 fn get_next_input() -> io::Result<Option<String>> {
@@ -31,9 +39,15 @@ fn get_next_input() -> io::Result<Option<String>> {
 pub trait Round {
     const ROUND_LABEL: &'static str;
 
-    fn play(&self, scorekeeper: &mut Scorekeeper) {
+    fn play(&self, scorekeeper: &mut Scorekeeper, player: Option<&mut Player>) {
         print!("{} - {}: ", Self::ROUND_LABEL, self.prompt());
         io::stdout().flush().unwrap();
+        if let Some(player) = player {
+            match self.sound() {
+                Ok(events) => player.play(&events),
+                Err(e) => eprintln!("  Couldn't play the prompt: {}.", e),
+            }
+        }
 
         let input = match get_next_input().unwrap() {
             Some(s) => s,
@@ -43,6 +57,8 @@ pub trait Round {
         println!("  {}", self.evaluate(input, scorekeeper));
     }
     fn prompt(&self) -> String;
+    /// The prompt as groups of pitches to play, one group after another.
+    fn sound(&self) -> Result<Vec<Vec<Pitch>>, UnspellableNote>;
     fn evaluate(&self, input: String, scorekeeper: &mut Scorekeeper) -> String;
 }
 
@@ -50,15 +66,62 @@ pub struct IntervalsRound {
     root_note: Note,
     descending: bool,
     interval: CanonicalInterval,
+    /// How wide the interval is in the tuning given with --tuning, in cents.
+    cents: Option<f64>,
 }
 
 impl IntervalsRound {
-    pub fn new(rng: &mut impl Rng) -> Self {
-        IntervalsRound {
+    pub fn new(rng: &mut impl Rng, tuning: Option<&ScalaTuning>) -> Self {
+        let mut round = IntervalsRound {
             root_note: STANDARD_NOTES.choose(rng).cloned().unwrap(),
             descending: rng.gen(),
             interval: STANDARD_INTERVALS.choose(rng).cloned().unwrap(),
+            cents: None,
+        };
+        round.cents = tuning.and_then(|tuning| round.cents_in(tuning));
+        round
+    }
+
+    // Measured from the root in octave 4, or `None` if either pitch is
+    // unmapped.
+    fn cents_in(&self, tuning: &ScalaTuning) -> Option<f64> {
+        let root = Pitch(self.root_note, 4);
+        let interval = Interval::from(self.interval);
+        let other = if self.descending {
+            root.transpose_down(interval)
+        } else {
+            root.transpose(interval)
+        };
+
+        let root = tuning.key_frequency(root.semitones())?;
+        let other = tuning.key_frequency(other.ok()?.semitones())?;
+        Some(tuning::cents(other / root).abs())
+    }
+}
+
+impl IntervalsRound {
+    // A tritone may be spelled as either an augmented fourth or a diminished
+    // fifth; every other canonical interval has a single spelling.
+    fn expected_notes(&self) -> Vec<Note> {
+        let mut intervals = vec![Interval::from(self.interval)];
+        if self.interval == CanonicalInterval::Tritone {
+            intervals.push(Interval::from_number_and_semitones(5, 6).unwrap());
         }
+
+        intervals
+            .into_iter()
+            .map(|interval| {
+                if self.descending {
+                    self.root_note
+                        .transpose_down(interval)
+                        .unwrap_or(self.root_note - self.interval)
+                } else {
+                    self.root_note
+                        .transpose(interval)
+                        .unwrap_or(self.root_note + self.interval)
+                }
+            })
+            .collect()
     }
 }
 
@@ -74,37 +137,48 @@ impl Round for IntervalsRound {
         )
     }
 
-    fn evaluate(&self, input: String, scorekeeper: &mut Scorekeeper) -> String {
-        let expected = if self.descending {
-            self.root_note - self.interval
+    fn sound(&self) -> Result<Vec<Vec<Pitch>>, UnspellableNote> {
+        let style = if self.descending {
+            IntervalStyle::Descending
         } else {
-            self.root_note + self.interval
+            IntervalStyle::Ascending
         };
+        audio::interval_events(Pitch(self.root_note, 4), self.interval.into(), style)
+    }
+
+    fn evaluate(&self, input: String, scorekeeper: &mut Scorekeeper) -> String {
+        let expected = self.expected_notes();
 
         let correct = match input.parse::<Note>() {
-            Ok(input_note) => input_note == expected,
-            Err(e) => return format!("Error: {:?}.", e),
+            Ok(input_note) => expected.contains(&input_note),
+            Err(e) => return format!("Error: {}.", e),
         };
+        let expected = expected[0];
 
         let duration = scorekeeper.add_result(correct);
-        if correct {
+        let mut result = if correct {
             format!("Correct! ({:.2} sec.)", duration.as_secs_f32())
         } else {
             format!("Incorrect! (Expected {}.)", expected)
+        };
+        if let Some(cents) = self.cents {
+            result.push_str(&format!(" It's {:.0} cents wide in this tuning.", cents));
         }
+        result
     }
 }
 
 pub struct ChordsRound {
-    root_note: Note,
-    chord: ChordQuality,
+    chord: ChordSymbol,
 }
 
 impl ChordsRound {
-    pub fn new(rng: &mut impl Rng) -> Self {
+    pub fn new(rng: &mut impl Rng, chords: ChordSet) -> Self {
         ChordsRound {
-            root_note: STANDARD_NOTES.choose(rng).cloned().unwrap(),
-            chord: STANDARD_CHORD_QUALITIES.choose(rng).cloned().unwrap(),
+            chord: ChordSymbol::new(
+                STANDARD_NOTES.choose(rng).cloned().unwrap(),
+                chords.qualities().choose(rng).cloned().unwrap(),
+            ),
         }
     }
 }
@@ -113,7 +187,12 @@ impl Round for ChordsRound {
     const ROUND_LABEL: &'static str = "Chord";
 
     fn prompt(&self) -> String {
-        format!("{} {}", self.root_note, self.chord)
+        self.chord.to_string()
+    }
+
+    fn sound(&self) -> Result<Vec<Vec<Pitch>>, UnspellableNote> {
+        let notes = self.chord.spell();
+        Ok(vec![audio::ascending(Pitch(notes[0], 3), &notes)])
     }
 
     fn evaluate(&self, input: String, scorekeeper: &mut Scorekeeper) -> String {
@@ -121,12 +200,12 @@ impl Round for ChordsRound {
 
         match notes {
             Ok(notes) => {
-                let expected = self.chord.spell(self.root_note);
+                let expected = self.chord.spell();
                 let correct = notes == expected;
                 let duration = scorekeeper.add_result(correct);
                 feedback_expected_notes(expected, correct, duration)
             }
-            Err(e) => format!("Error: {:?}.", e),
+            Err(e) => format!("Error: {}.", e),
         }
     }
 }
@@ -142,13 +221,20 @@ impl ScalesRound {
         let scale = if rng.gen() {
             Left(STANDARD_SCALES.choose(rng).cloned().unwrap())
         } else {
-            let major = STANDARD_SCALES[0].clone();
-            let mode = Mode::from(rng.gen_range(0, 7));
-            Right(ModalScale::new(major, mode))
+            let parent = STANDARD_SCALES.choose(rng).cloned().unwrap();
+            let mode = Mode::new(rng.gen_range(0, parent.intervals.len() as u8));
+            Right(ModalScale::new(parent, mode).unwrap())
         };
 
         ScalesRound { root_note, scale }
     }
+
+    fn expected_notes(&self) -> Vec<Note> {
+        match &self.scale {
+            Left(scale) => scale.spell(self.root_note),
+            Right(modal_scale) => modal_scale.spell(self.root_note),
+        }
+    }
 }
 
 impl Round for ScalesRound {
@@ -160,20 +246,26 @@ impl Round for ScalesRound {
             self.root_note,
             match &self.scale {
                 Left(scale) => Cow::Borrowed(&scale.name),
-                Right(modal_scale) => Cow::Owned(format!("{:?}", &modal_scale.mode)),
+                Right(modal_scale) => Cow::Owned(modal_scale.name()),
             }
         )
     }
 
+    fn sound(&self) -> Result<Vec<Vec<Pitch>>, UnspellableNote> {
+        let mut notes = self.expected_notes();
+        notes.push(self.root_note);
+        Ok(audio::ascending(Pitch(self.root_note, 4), &notes)
+            .into_iter()
+            .map(|pitch| vec![pitch])
+            .collect())
+    }
+
     fn evaluate(&self, input: String, scorekeeper: &mut Scorekeeper) -> String {
         let notes = notes_from_input(input);
 
         match notes {
             Ok(notes) => {
-                let expected = match &self.scale {
-                    Left(scale) => scale.spell(self.root_note),
-                    Right(modal_scale) => modal_scale.spell(self.root_note),
-                };
+                let expected = self.expected_notes();
                 let expected_with_root: Vec<_> =
                     expected.iter().chain(Some(&self.root_note)).collect();
 
@@ -182,12 +274,12 @@ impl Round for ScalesRound {
                 let duration = scorekeeper.add_result(correct);
                 feedback_expected_notes(expected, correct, duration)
             }
-            Err(e) => format!("Error: {:?}.", e),
+            Err(e) => format!("Error: {}.", e),
         }
     }
 }
 
-fn notes_from_input(input: String) -> Result<Vec<Note>, UnrecognizedNote> {
+fn notes_from_input(input: String) -> Result<Vec<Note>, ParseNoteError> {
     let notes = input
         .trim()
         .split_ascii_whitespace()
@@ -215,3 +307,111 @@ fn feedback_expected_notes(expected: Vec<Note>, correct: bool, duration: Duratio
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use music_exercises::audio::sink::{AudioSink, NullSink, SinkError};
+    use music_exercises::audio::{Audio, Renderer};
+    use music_exercises::scale::catalogue;
+
+    use super::*;
+
+    // Shares a `NullSink` with the test, since the player owns its sink.
+    struct Shared(Rc<RefCell<NullSink>>);
+
+    impl AudioSink for Shared {
+        fn play(&mut self, audio: &Audio) -> Result<(), SinkError> {
+            self.0.borrow_mut().play(audio)
+        }
+    }
+
+    fn renderer() -> Renderer {
+        Renderer {
+            sample_rate: 8000,
+            ..Renderer::default()
+        }
+    }
+
+    fn pitches(s: &str) -> Vec<Pitch> {
+        s.split_whitespace().map(|s| s.parse().unwrap()).collect()
+    }
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    // Plays the round through a player, returning what it sounded and the
+    // audio the sink was given.
+    fn play(round: &impl Round) -> (Vec<Vec<Pitch>>, Audio) {
+        let sink = Rc::new(RefCell::new(NullSink::new()));
+        let mut player = Player::new(renderer(), Box::new(Shared(sink.clone())));
+        let events = round.sound().unwrap();
+        player.play(&events);
+
+        let mut played = sink.borrow().played.clone();
+        assert_eq!(played.len(), 1);
+        (events, played.remove(0))
+    }
+
+    #[test]
+    fn test_intervals_round() {
+        let round = IntervalsRound {
+            root_note: note("Eb"),
+            descending: false,
+            interval: CanonicalInterval::Tritone,
+            cents: None,
+        };
+        let (events, audio) = play(&round);
+        assert_eq!(events, [pitches("Eb4"), pitches("A4")]);
+        assert_eq!(
+            audio,
+            renderer()
+                .render_interval(
+                    pitches("Eb4")[0],
+                    "A4".parse().unwrap(),
+                    IntervalStyle::Ascending
+                )
+                .unwrap()
+        );
+
+        let round = IntervalsRound {
+            root_note: note("C"),
+            descending: true,
+            interval: CanonicalInterval::MajorThird,
+            cents: None,
+        };
+        let (events, audio) = play(&round);
+        assert_eq!(events, [pitches("C4"), pitches("Ab3")]);
+        assert_eq!(audio, renderer().render_melody(&pitches("C4 Ab3")));
+    }
+
+    #[test]
+    fn test_chords_round() {
+        let round = ChordsRound {
+            chord: "F#m7b5".parse().unwrap(),
+        };
+        let (events, audio) = play(&round);
+        assert_eq!(events, [pitches("F#3 A3 C4 E4")]);
+        assert_eq!(audio, renderer().render_chord(&pitches("F#3 A3 C4 E4")));
+    }
+
+    #[test]
+    fn test_scales_round() {
+        let round = ScalesRound {
+            root_note: note("A"),
+            scale: Left(catalogue::find("Harmonic Minor").unwrap()),
+        };
+        let (events, audio) = play(&round);
+        let expected = pitches("A4 B4 C5 D5 E5 F5 G#5 A5");
+        assert_eq!(events.concat(), expected);
+        assert!(events.iter().all(|event| event.len() == 1));
+        assert_eq!(audio, renderer().render_melody(&expected));
+        assert_eq!(
+            audio,
+            renderer().render_scale(expected[0], &round.expected_notes())
+        );
+    }
+}
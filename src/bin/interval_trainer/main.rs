@@ -1,7 +1,18 @@
+use std::fmt::Display;
 use std::ops::DerefMut;
+use std::path::PathBuf;
+use std::process;
 use std::sync::{Arc, Mutex};
 
+use music_exercises::audio::sink::{AudioSink, WavSink};
+use music_exercises::audio::synth::Voice;
+use music_exercises::audio::wav::SampleFormat;
+use music_exercises::audio::Renderer;
+use music_exercises::tuning::scala::{KeyboardMapping, ScalaScale, ScalaTuning};
+
+use crate::game::chord_set::ChordSet;
 use crate::game::mode::GameMode;
+use crate::game::player::{AudioOutput, Player};
 use crate::game::scorekeeper::Scorekeeper;
 
 mod game;
@@ -11,14 +22,91 @@ mod game;
 struct Options {
     #[structopt(default_value)]
     mode: GameMode,
+
+    /// The chord qualities to practise: standard, extended, altered or all.
+    #[structopt(long, default_value)]
+    chords: ChordSet,
+
+    /// Where to play each prompt: off, device (the sound card, if built with
+    /// the playback feature) or wav (numbered files in --wav-dir).
+    #[structopt(long, default_value)]
+    audio: AudioOutput,
+
+    /// The directory that wav audio output is written to.
+    #[structopt(long, default_value = "prompts", parse(from_os_str))]
+    wav_dir: PathBuf,
+
+    /// The synth voice to play prompts with: sine, additive or plucked.
+    #[structopt(long, default_value)]
+    voice: Voice,
+
+    /// A Scala .scl tuning to play prompts in, instead of equal temperament.
+    /// Interval rounds also report how many cents wide their interval is in it.
+    #[structopt(long, parse(from_os_str))]
+    tuning: Option<PathBuf>,
+
+    /// A Scala .kbm keyboard mapping for --tuning.
+    #[structopt(long, parse(from_os_str), requires = "tuning")]
+    keyboard_mapping: Option<PathBuf>,
 }
 
 impl Default for Options {
     fn default() -> Self {
         Options {
             mode: GameMode::Mixed,
+            chords: ChordSet::Standard,
+            audio: AudioOutput::Off,
+            wav_dir: PathBuf::from("prompts"),
+            voice: Voice::Sine,
+            tuning: None,
+            keyboard_mapping: None,
+        }
+    }
+}
+
+fn exit_with_error(error: impl Display) -> ! {
+    eprintln!("Error: {}.", error);
+    process::exit(1);
+}
+
+/// The Scala tuning given by --tuning and --keyboard-mapping.
+fn tuning(options: &Options) -> Option<ScalaTuning> {
+    let path = options.tuning.as_ref()?;
+    let scale = ScalaScale::load(path).unwrap_or_else(|e| exit_with_error(e));
+    let mapping = match &options.keyboard_mapping {
+        Some(path) => KeyboardMapping::load(path).unwrap_or_else(|e| exit_with_error(e)),
+        None => KeyboardMapping::default(),
+    };
+    Some(ScalaTuning::new(scale, mapping))
+}
+
+fn player(options: &Options, tuning: Option<&ScalaTuning>) -> Option<Player> {
+    let sink: Box<dyn AudioSink> = match options.audio {
+        AudioOutput::Off => return None,
+        #[cfg(feature = "playback")]
+        AudioOutput::Device => {
+            use music_exercises::audio::device::DeviceSink;
+            Box::new(DeviceSink::new().unwrap_or_else(|e| exit_with_error(e)))
+        }
+        #[cfg(not(feature = "playback"))]
+        AudioOutput::Device => {
+            exit_with_error("device audio needs a build with the playback feature")
         }
+        AudioOutput::Wav => Box::new(
+            WavSink::new(&options.wav_dir, SampleFormat::Int16)
+                .unwrap_or_else(|e| exit_with_error(e)),
+        ),
+    };
+
+    let mut renderer = Renderer {
+        voice: options.voice,
+        ..Renderer::default()
+    };
+    if let Some(tuning) = tuning {
+        renderer.tuning = Box::new(tuning.clone());
     }
+
+    Some(Player::new(renderer, sink))
 }
 
 #[paw::main]
@@ -33,11 +121,17 @@ fn main(options: Options) {
         .expect("Error setting Ctrl-C handler.");
     }
 
+    let tuning = tuning(&options);
+    let mut player = player(&options, tuning.as_ref());
     let mut rng = rand::thread_rng();
 
     loop {
-        options
-            .mode
-            .play_round(&mut rng, scorekeeper.lock().unwrap().deref_mut());
+        options.mode.play_round(
+            &mut rng,
+            options.chords,
+            scorekeeper.lock().unwrap().deref_mut(),
+            tuning.as_ref(),
+            player.as_mut(),
+        );
     }
 }
@@ -1 +1,5 @@
+pub mod identify;
 pub mod quality;
+pub mod registry;
+pub mod symbol;
+pub mod voicing;
@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::accidental::Accidental;
+use crate::chord::registry::{ChordDefinition, DEFINITIONS};
+use crate::chord::symbol::ChordSymbol;
+use crate::degree::Degree;
+use crate::note::Note;
+
+/// One reading of a set of notes as a chord.
+#[derive(fmt::Debug, Clone)]
+pub struct ChordIdentification {
+    pub root: Note,
+    pub definition: &'static ChordDefinition,
+    pub bass: Note,
+    /// Which chord tone is in the bass, counting the root as 0, or `None` if
+    /// the bass is an extension or not in the chord at all.
+    pub inversion: Option<usize>,
+    /// Chord tones that weren't among the notes: the root or optional tones.
+    pub missing: Vec<Degree>,
+    /// How far the reading strays from the plain chord; lower is better.
+    pub penalty: u32,
+}
+
+impl ChordIdentification {
+    pub fn symbol(&self) -> ChordSymbol {
+        let symbol = ChordSymbol::new(self.root, self.definition.quality());
+        if self.bass == self.root {
+            symbol
+        } else {
+            symbol.with_bass(self.bass)
+        }
+    }
+}
+
+// Penalties for each way a reading can depart from the plain chord.
+const MISSING_ROOT: u32 = 4;
+const MISSING_OPTIONAL: u32 = 1;
+const INVERTED: u32 = 1;
+const FOREIGN_BASS: u32 = 3;
+const MISSPELLED: u32 = 2;
+
+/// Every chord the notes could spell, best first. The first note is taken as
+/// the bass; the rest may come in any order. A reading may leave out the root
+/// or the chord's optional tones, and a bass note that isn't in the chord is
+/// read as a slash chord.
+pub fn identify(notes: &[Note]) -> Vec<ChordIdentification> {
+    let bass = match notes.first() {
+        Some(&bass) => bass,
+        None => return Vec::new(),
+    };
+
+    let mut candidates = HashSet::new();
+    for (index, definition) in DEFINITIONS.iter().enumerate() {
+        for degree in definition.quality().degrees() {
+            for note in notes {
+                if let Ok(root) = note.transpose_down(degree.interval()) {
+                    candidates.insert((index, root));
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<(usize, ChordIdentification)> = candidates
+        .into_iter()
+        .filter_map(|(index, root)| {
+            reading(notes, bass, root, &DEFINITIONS[index]).map(|reading| (index, reading))
+        })
+        .collect();
+
+    results.sort_by_key(|(index, reading)| {
+        (
+            reading.penalty,
+            reading.definition.quality().note_count(),
+            *index,
+            reading.root,
+        )
+    });
+    results.into_iter().map(|(_, reading)| reading).collect()
+}
+
+fn reading(
+    notes: &[Note],
+    bass: Note,
+    root: Note,
+    definition: &'static ChordDefinition,
+) -> Option<ChordIdentification> {
+    let quality = definition.quality();
+    let degrees = quality.degrees();
+    let tones = quality.spell(root);
+
+    let mut penalty = 0;
+    let mut found = vec![false; tones.len()];
+    let mut foreign_bass = false;
+
+    for (i, &note) in notes.iter().enumerate() {
+        match tones.iter().position(|tone| tone.is_enharmonic_to(note)) {
+            Some(index) => {
+                found[index] = true;
+                if tones[index] != note {
+                    penalty += MISSPELLED;
+                }
+            }
+            None if i == 0 => foreign_bass = true,
+            None => return None,
+        }
+    }
+
+    let optional = definition.optional();
+    let mut missing = Vec::new();
+    for (&degree, _) in degrees.iter().zip(found).filter(|&(_, found)| !found) {
+        if degree.number() == 1 && degree.accidental() == Accidental::Natural {
+            penalty += MISSING_ROOT;
+        } else if optional.contains(&degree) {
+            penalty += MISSING_OPTIONAL;
+        } else {
+            return None;
+        }
+        missing.push(degree);
+    }
+
+    // A chord reduced to one or two notes could be almost anything.
+    if missing.len() * 2 >= degrees.len() {
+        return None;
+    }
+
+    let inversion = if foreign_bass {
+        penalty += FOREIGN_BASS;
+        None
+    } else {
+        let index = tones
+            .iter()
+            .position(|tone| tone.is_enharmonic_to(bass))
+            .unwrap();
+        if degrees[index].number() <= 7 {
+            if index > 0 {
+                penalty += INVERTED;
+            }
+            Some(index)
+        } else {
+            penalty += FOREIGN_BASS;
+            None
+        }
+    };
+
+    Some(ChordIdentification {
+        root,
+        definition,
+        bass,
+        inversion,
+        missing,
+        penalty,
+    })
+}
+
+/// Describes the reading in words, e.g. "C major, first inversion" or "C
+/// dominant seventh over F#, no fifth".
+impl fmt::Display for ChordIdentification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const INVERSIONS: [&str; 4] = ["", "first", "second", "third"];
+
+        write!(f, "{} {}", self.root, self.definition.name)?;
+        match self.inversion {
+            Some(0) => {}
+            Some(n) if n < INVERSIONS.len() => write!(f, ", {} inversion", INVERSIONS[n])?,
+            _ => write!(f, " over {}", self.bass)?,
+        }
+
+        for &degree in &self.missing {
+            write!(f, ", no {}", tone_name(degree))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn tone_name(degree: Degree) -> String {
+    const NAMES: [&str; 13] = [
+        "root",
+        "second",
+        "third",
+        "fourth",
+        "fifth",
+        "sixth",
+        "seventh",
+        "octave",
+        "ninth",
+        "tenth",
+        "eleventh",
+        "twelfth",
+        "thirteenth",
+    ];
+
+    let name = NAMES
+        .get(degree.number() as usize - 1)
+        .map_or_else(|| degree.number().to_string(), |name| name.to_string());
+    match degree.accidental() {
+        Accidental::DoubleFlat => format!("double-flat {}", name),
+        Accidental::Flat => format!("flat {}", name),
+        Accidental::Natural => name,
+        Accidental::Sharp => format!("sharp {}", name),
+        Accidental::DoubleSharp => format!("double-sharp {}", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identify(notes: &str) -> Vec<ChordIdentification> {
+        let notes: Vec<Note> = notes
+            .split_whitespace()
+            .map(|note| note.parse().unwrap())
+            .collect();
+        super::identify(&notes)
+    }
+
+    fn best(notes: &str) -> String {
+        identify(notes)[0].to_string()
+    }
+
+    #[test]
+    fn test_identify() {
+        assert_eq!(best("C E G"), "C major");
+        assert_eq!(best("E G C"), "C major, first inversion");
+        assert_eq!(best("G C E"), "C major, second inversion");
+        assert_eq!(best("C E G Bb D"), "C dominant ninth");
+        assert_eq!(identify("C E G Bb D")[0].symbol().to_string(), "C9");
+        assert_eq!(best("Bb C E G"), "C dominant seventh, third inversion");
+        assert_eq!(best("F# A C Eb"), "F# diminished seventh");
+        assert_eq!(best("C Eb Gb Bbb"), "C diminished seventh");
+        assert_eq!(best("Db F Ab Cb"), "Db dominant seventh");
+        assert_eq!(best("B D F Ab"), "B diminished seventh");
+
+        let first_inversion = &identify("E G C")[0];
+        assert_eq!(first_inversion.inversion, Some(1));
+        assert_eq!(first_inversion.symbol().to_string(), "C/E");
+    }
+
+    #[test]
+    fn test_incomplete_and_slash_chords() {
+        assert_eq!(best("C E Bb"), "C dominant seventh, no fifth");
+        assert_eq!(best("C E B D"), "C major ninth, no fifth");
+        assert_eq!(best("F# C E G"), "C major over F#");
+        assert_eq!(identify("F# C E G")[0].symbol().to_string(), "C/F#");
+
+        // A rootless voicing is still found, behind the chord it spells on
+        // its own.
+        let readings = identify("E G Bb D");
+        assert_eq!(readings[0].to_string(), "E half-diminished seventh");
+        assert!(readings
+            .iter()
+            .any(|reading| reading.to_string() == "C dominant ninth, first inversion, no root"));
+
+        assert_eq!(best("C E Bb D F"), "C dominant eleventh, no fifth");
+        assert!(identify("C Bb D F")
+            .iter()
+            .any(|reading| reading.to_string() == "C dominant eleventh, no third, no fifth"));
+        assert_eq!(best("C F G Bb D"), "C dominant ninth suspended fourth");
+
+        assert!(identify("").is_empty());
+    }
+
+    #[test]
+    fn test_misspelled_notes_rank_lower() {
+        // Spelled as written, this is a C augmented triad; read from E or Ab
+        // it needs respelling.
+        let readings = identify("C E G#");
+        assert_eq!(readings[0].to_string(), "C augmented");
+        assert!(readings[0].penalty < readings[1].penalty);
+    }
+}
@@ -1,66 +1,154 @@
 use std::fmt;
 
-use crate::interval::canonical::CanonicalInterval;
-use crate::note::Note;
+use crate::chord::registry;
+use crate::chord::symbol::SymbolStyle;
+use crate::degree::{self, Degree, UnrecognizedDegree};
+use crate::interval::Interval;
+use crate::note::{Note, UnspellableNote};
 
-#[derive(fmt::Debug, Clone, PartialEq)]
+/// A chord quality as a list of chord tones above the root, e.g. 1 b3 b5 bb7
+/// for a diminished seventh.
+#[derive(fmt::Debug, Clone, Eq, PartialEq, Hash)]
 pub struct ChordQuality {
-    name: String,
-    intervals: Vec<CanonicalInterval>,
+    degrees: Vec<Degree>,
 }
 
 #[allow(dead_code)]
 impl ChordQuality {
-    pub fn new(name: impl ToString, intervals: impl AsRef<[CanonicalInterval]>) -> Self {
+    pub fn new(degrees: impl AsRef<[Degree]>) -> Self {
         ChordQuality {
-            name: name.to_string(),
-            intervals: Vec::from(intervals.as_ref()),
+            degrees: Vec::from(degrees.as_ref()),
         }
     }
 
-    pub fn from_intervals(name: impl ToString, intervals: impl AsRef<[u8]>) -> Self {
-        ChordQuality {
-            name: name.to_string(),
-            intervals: intervals
-                .as_ref()
+    /// Parses the chord tones from a list like "1 3 5 b7 9 #11".
+    pub fn from_degrees(degrees: &str) -> Result<Self, UnrecognizedDegree> {
+        Ok(ChordQuality::new(degree::parse_degrees(degrees)?))
+    }
+
+    pub fn degrees(&self) -> &[Degree] {
+        &self.degrees
+    }
+
+    /// The interval from the root to each chord tone.
+    pub fn intervals(&self) -> Vec<Interval> {
+        self.degrees
+            .iter()
+            .map(|degree| degree.interval())
+            .collect()
+    }
+
+    pub fn note_count(&self) -> usize {
+        self.degrees.len()
+    }
+
+    /// Chord tones that can be left out of a voicing, as listed in the
+    /// registry. A quality that isn't registered can only lose its fifth.
+    pub fn optional(&self) -> Vec<Degree> {
+        match registry::lookup(self) {
+            Some(definition) => definition.optional(),
+            None => self
+                .degrees
                 .iter()
                 .cloned()
-                .map(CanonicalInterval::from)
+                .filter(|&d| d == degree::natural(5))
                 .collect(),
         }
     }
 
-    pub fn intervals(&self) -> &[CanonicalInterval] {
-        &self.intervals
+    /// Chord tones that give the chord its identity, as listed in the
+    /// registry. For a quality that isn't registered, that's everything but
+    /// the root and the fifth.
+    pub fn characteristic(&self) -> Vec<Degree> {
+        match registry::lookup(self) {
+            Some(definition) => definition.characteristic(),
+            None => self
+                .degrees
+                .iter()
+                .cloned()
+                .filter(|&d| d.number() != 1 && d != degree::natural(5))
+                .collect(),
+        }
     }
 
-    pub fn note_count(&self) -> usize {
-        self.intervals.len() + 1
+    /// The chord tone with the given number, whatever its alteration, so that
+    /// asking a diminished seventh for its 5 finds b5. A 9, 11 or 13 is also
+    /// found by its simple number.
+    pub fn degree(&self, number: u8) -> Option<Degree> {
+        let simple = |number: u8| (number.max(1) - 1) % 7;
+
+        self.degrees
+            .iter()
+            .find(|degree| degree.number() == number)
+            .or_else(|| {
+                self.degrees
+                    .iter()
+                    .find(|degree| simple(degree.number()) == simple(number))
+            })
+            .cloned()
+    }
+
+    /// The note playing the given chord tone above `root`, e.g. the 7 of an Ab
+    /// dominant seventh is Gb.
+    pub fn tone(&self, root: Note, number: u8) -> Option<Result<Note, UnspellableNote>> {
+        self.degree(number)
+            .map(|degree| root.transpose(degree.interval()))
     }
 
+    /// Spells the chord from `root`, respelling enharmonically any tone that
+    /// would need more than a double accidental (see `try_spell`).
     pub fn spell(&self, root: Note) -> Vec<Note> {
-        let mut result = vec![root];
-        let mut note = root;
-        for interval in self.intervals.iter() {
-            note = note + *interval;
-            result.push(note);
-        }
-        result
+        self.degrees
+            .iter()
+            .map(|degree| {
+                root.transpose(degree.interval())
+                    .unwrap_or_else(|e| Note::from_pitch(e.pitch).unwrap())
+            })
+            .collect()
+    }
+
+    pub fn try_spell(&self, root: Note) -> Result<Vec<Note>, UnspellableNote> {
+        self.degrees
+            .iter()
+            .map(|degree| root.transpose(degree.interval()))
+            .collect()
     }
 }
 
+/// Formats the quality as a chord symbol without its root, e.g. "m7b5", or with
+/// `{:#}` in jazz style, e.g. "ø7".
 impl fmt::Display for ChordQuality {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", &self.name)
+        let style = if f.alternate() {
+            SymbolStyle::Jazz
+        } else {
+            SymbolStyle::Plain
+        };
+        write!(f, "{}", self.symbol(style))
     }
 }
 
 macro_rules! define_chords {
-    ($($name:ident = $label:literal [$($interval:expr),+],)*) => {
+    ($($name:ident = $degrees:literal,)*) => {
+        $(
+            pub static $name: ::once_cell::sync::Lazy<$crate::chord::quality::ChordQuality> =
+                ::once_cell::sync::Lazy::new(|| {
+                    $crate::chord::quality::ChordQuality::from_degrees($degrees).unwrap()
+                });
+        )*
+    };
+}
+
+// Chords defined in the registry, looked up by name so that their degrees are
+// only written down once.
+macro_rules! registered_chords {
+    ($($name:ident = $definition:literal,)*) => {
         $(
             pub static $name: ::once_cell::sync::Lazy<$crate::chord::quality::ChordQuality> =
                 ::once_cell::sync::Lazy::new(|| {
-                    $crate::chord::quality::ChordQuality::from_intervals($label, &[$($interval),+])
+                    $crate::chord::registry::find($definition)
+                        .expect("chord must be in the registry")
+                        .quality()
                 });
         )*
     };
@@ -69,27 +157,212 @@ macro_rules! define_chords {
 #[allow(dead_code)]
 pub mod triads {
     define_chords! {
-        MAJOR = "Maj" [4, 3],
-        MINOR = "Min" [3, 4],
-        DIMINISHED = "Dim" [3, 3],
-        AUGMENTED = "Aug" [4, 4],
-        SUSPENDED_2 = "Sus2" [2, 5],
-        PHRYGIAN = "Phr" [1, 6],
-        SUSPENDED_4 = "Sus4" [5, 2],
-        LYDIAN = "Lyd" [6, 1],
+        MAJOR = "1 3 5",
+        MINOR = "1 b3 5",
+        DIMINISHED = "1 b3 b5",
+        AUGMENTED = "1 3 #5",
+        SUSPENDED_2 = "1 2 5",
+        PHRYGIAN = "1 b2 5",
+        SUSPENDED_4 = "1 4 5",
+        LYDIAN = "1 #4 5",
     }
 }
 
 #[allow(dead_code)]
 pub mod sevenths {
     define_chords! {
-        DOMINANT = "Dom7" [4, 3, 3],
-        MAJOR = "Maj7" [4, 3, 4],
-        MINOR = "Min7" [3, 4, 3],
-        DIMINISHED = "Dim7" [3, 3, 3],
-        HALF_DIMINISHED = "Min7(b5)" [3, 3, 4],
-        AUGMENTED_DOMINANT = "Dom7(#5)" [4, 4, 2],
-        AUGMENTED_MAJOR = "Maj7(#5)" [4, 4, 3],
-        DIMINISHED_MAJOR = "Dim(Maj7)" [3, 3, 7],
+        DOMINANT = "1 3 5 b7",
+        MAJOR = "1 3 5 7",
+        MINOR = "1 b3 5 b7",
+        DIMINISHED = "1 b3 b5 bb7",
+        HALF_DIMINISHED = "1 b3 b5 b7",
+        AUGMENTED_DOMINANT = "1 3 #5 b7",
+        AUGMENTED_MAJOR = "1 3 #5 7",
+        DIMINISHED_MAJOR = "1 b3 b5 7",
+    }
+
+    registered_chords! {
+        SUSPENDED_4 = "dominant seventh suspended fourth",
+    }
+}
+
+#[allow(dead_code)]
+pub mod sixths {
+    registered_chords! {
+        MAJOR = "major sixth",
+        MINOR = "minor sixth",
+        SIX_NINE = "six-nine",
+        MINOR_SIX_NINE = "minor six-nine",
+    }
+}
+
+#[allow(dead_code)]
+pub mod added {
+    registered_chords! {
+        NINTH = "added ninth",
+        MINOR_NINTH = "minor added ninth",
+    }
+}
+
+#[allow(dead_code)]
+pub mod ninths {
+    registered_chords! {
+        DOMINANT = "dominant ninth",
+        MAJOR = "major ninth",
+        MINOR = "minor ninth",
+        SUSPENDED_4 = "dominant ninth suspended fourth",
+    }
+}
+
+#[allow(dead_code)]
+pub mod elevenths {
+    registered_chords! {
+        DOMINANT = "dominant eleventh",
+        MINOR = "minor eleventh",
+    }
+}
+
+#[allow(dead_code)]
+pub mod thirteenths {
+    registered_chords! {
+        DOMINANT = "dominant thirteenth",
+        MAJOR = "major thirteenth",
+        MINOR = "minor thirteenth",
+        SUSPENDED_4 = "dominant thirteenth suspended fourth",
+    }
+}
+
+#[allow(dead_code)]
+pub mod altered {
+    registered_chords! {
+        FLAT_9 = "dominant seventh flat ninth",
+        SHARP_9 = "dominant seventh sharp ninth",
+        SHARP_11 = "dominant seventh sharp eleventh",
+        FLAT_13 = "dominant seventh flat thirteenth",
+        ALTERED = "altered dominant",
+        MAJOR_SHARP_11 = "major seventh sharp eleventh",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    fn degree(s: &str) -> Degree {
+        s.parse().unwrap()
+    }
+
+    fn spell(chord: &ChordQuality, root: &str) -> String {
+        chord
+            .spell(note(root))
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_spell() {
+        assert_eq!(spell(&sevenths::DIMINISHED, "C"), "C Eb Gb Bbb");
+        assert_eq!(spell(&sevenths::DOMINANT, "Ab"), "Ab C Eb Gb");
+        assert_eq!(spell(&sevenths::HALF_DIMINISHED, "F#"), "F# A C E");
+        assert_eq!(spell(&sevenths::DIMINISHED_MAJOR, "D"), "D F Ab C#");
+        assert_eq!(spell(&triads::AUGMENTED, "B"), "B D# F##");
+        assert_eq!(spell(&triads::MINOR, "Eb"), "Eb Gb Bb");
+        assert_eq!(spell(&triads::LYDIAN, "F"), "F B C");
+
+        let chord = ChordQuality::from_degrees("1 3 5 b7 9 #11").unwrap();
+        assert_eq!(spell(&chord, "C"), "C E G Bb D F#");
+
+        assert!(sevenths::DIMINISHED.try_spell(note("Cb")).is_err());
+        assert_eq!(spell(&sevenths::DIMINISHED, "Cb"), "Cb Ebb Gbb G#");
+    }
+
+    #[test]
+    fn test_extended_and_altered() {
+        assert_eq!(spell(&sixths::SIX_NINE, "Eb"), "Eb G Bb C F");
+        assert_eq!(spell(&ninths::MINOR, "F#"), "F# A C# E G#");
+        assert_eq!(spell(&elevenths::DOMINANT, "Bb"), "Bb D F Ab C Eb");
+        assert_eq!(spell(&thirteenths::DOMINANT, "G"), "G B D F A E");
+        assert_eq!(spell(&thirteenths::SUSPENDED_4, "D"), "D G A C E B");
+        assert_eq!(spell(&altered::FLAT_9, "A"), "A C# E G Bb");
+        assert_eq!(spell(&altered::SHARP_9, "E"), "E G# B D F##");
+        assert_eq!(spell(&altered::SHARP_11, "Db"), "Db F Ab Cb G");
+        assert_eq!(spell(&altered::FLAT_13, "C"), "C E G Bb Ab");
+        assert_eq!(spell(&altered::ALTERED, "G"), "G B F Ab A# C# Eb");
+        assert_eq!(spell(&altered::MAJOR_SHARP_11, "F"), "F A C E B");
+
+        // Every registered static is found in the registry.
+        let registered = [
+            &*sevenths::SUSPENDED_4,
+            &sixths::MAJOR,
+            &sixths::MINOR,
+            &sixths::SIX_NINE,
+            &sixths::MINOR_SIX_NINE,
+            &added::NINTH,
+            &added::MINOR_NINTH,
+            &ninths::DOMINANT,
+            &ninths::MAJOR,
+            &ninths::MINOR,
+            &ninths::SUSPENDED_4,
+            &elevenths::DOMINANT,
+            &elevenths::MINOR,
+            &thirteenths::DOMINANT,
+            &thirteenths::MAJOR,
+            &thirteenths::MINOR,
+            &thirteenths::SUSPENDED_4,
+            &altered::FLAT_9,
+            &altered::SHARP_9,
+            &altered::SHARP_11,
+            &altered::FLAT_13,
+            &altered::ALTERED,
+            &altered::MAJOR_SHARP_11,
+        ];
+        for quality in registered.iter() {
+            assert!(registry::lookup(quality).is_some(), "{}", quality);
+        }
+
+        assert_eq!(
+            altered::ALTERED.characteristic(),
+            [degree("3"), degree("b7")]
+        );
+        assert_eq!(
+            altered::ALTERED.optional(),
+            ["b9", "#9", "#11", "b13"]
+                .iter()
+                .map(|s| degree(s))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(thirteenths::DOMINANT.optional(), [degree("5"), degree("9")]);
+
+        let unregistered = ChordQuality::from_degrees("1 3 5 b7 b9 #11").unwrap();
+        assert_eq!(unregistered.optional(), [degree("5")]);
+        assert_eq!(
+            unregistered.characteristic(),
+            ["3", "b7", "b9", "#11"]
+                .iter()
+                .map(|s| degree(s))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_tones() {
+        let dominant = &sevenths::DOMINANT;
+        assert_eq!(dominant.tone(note("Ab"), 7), Some(Ok(note("Gb"))));
+        assert_eq!(dominant.tone(note("Ab"), 3), Some(Ok(note("C"))));
+        assert_eq!(dominant.tone(note("Ab"), 9), None);
+
+        assert_eq!(sevenths::DIMINISHED.degree(5), Some("b5".parse().unwrap()));
+        assert_eq!(sevenths::DIMINISHED.degree(7), Some("bb7".parse().unwrap()));
+
+        let chord = ChordQuality::from_degrees("1 3 5 b7 #11").unwrap();
+        assert_eq!(chord.degree(11), Some("#11".parse().unwrap()));
+        assert_eq!(chord.degree(4), Some("#11".parse().unwrap()));
+        assert_eq!(chord.tone(note("C"), 11), Some(Ok(note("F#"))));
     }
 }
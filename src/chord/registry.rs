@@ -0,0 +1,362 @@
+use crate::chord::quality::ChordQuality;
+use crate::chord::symbol::{ChordSymbol, SymbolStyle};
+use crate::degree::{self, Degree};
+
+/// A named chord quality, with its chord tones as degrees. Optional tones can
+/// be left out of a voicing without changing the chord's identity, as the
+/// fifth usually can; characteristic tones are the ones that make it that
+/// chord, such as the third and seventh of a dominant seventh.
+#[derive(Debug)]
+pub struct ChordDefinition {
+    pub name: &'static str,
+    pub degrees: &'static str,
+    pub optional: &'static str,
+    pub characteristic: &'static str,
+}
+
+pub static DEFINITIONS: &[ChordDefinition] = &[
+    ChordDefinition {
+        name: "major",
+        degrees: "1 3 5",
+        optional: "5",
+        characteristic: "3",
+    },
+    ChordDefinition {
+        name: "minor",
+        degrees: "1 b3 5",
+        optional: "5",
+        characteristic: "b3",
+    },
+    ChordDefinition {
+        name: "diminished",
+        degrees: "1 b3 b5",
+        optional: "",
+        characteristic: "b3 b5",
+    },
+    ChordDefinition {
+        name: "augmented",
+        degrees: "1 3 #5",
+        optional: "",
+        characteristic: "3 #5",
+    },
+    ChordDefinition {
+        name: "suspended second",
+        degrees: "1 2 5",
+        optional: "5",
+        characteristic: "2",
+    },
+    ChordDefinition {
+        name: "suspended fourth",
+        degrees: "1 4 5",
+        optional: "5",
+        characteristic: "4",
+    },
+    ChordDefinition {
+        name: "power chord",
+        degrees: "1 5",
+        optional: "",
+        characteristic: "5",
+    },
+    ChordDefinition {
+        name: "major sixth",
+        degrees: "1 3 5 6",
+        optional: "5",
+        characteristic: "3 6",
+    },
+    ChordDefinition {
+        name: "minor sixth",
+        degrees: "1 b3 5 6",
+        optional: "5",
+        characteristic: "b3 6",
+    },
+    ChordDefinition {
+        name: "six-nine",
+        degrees: "1 3 5 6 9",
+        optional: "5",
+        characteristic: "3 6 9",
+    },
+    ChordDefinition {
+        name: "minor six-nine",
+        degrees: "1 b3 5 6 9",
+        optional: "5",
+        characteristic: "b3 6 9",
+    },
+    ChordDefinition {
+        name: "dominant seventh",
+        degrees: "1 3 5 b7",
+        optional: "5",
+        characteristic: "3 b7",
+    },
+    ChordDefinition {
+        name: "major seventh",
+        degrees: "1 3 5 7",
+        optional: "5",
+        characteristic: "3 7",
+    },
+    ChordDefinition {
+        name: "minor seventh",
+        degrees: "1 b3 5 b7",
+        optional: "5",
+        characteristic: "b3 b7",
+    },
+    ChordDefinition {
+        name: "minor-major seventh",
+        degrees: "1 b3 5 7",
+        optional: "5",
+        characteristic: "b3 7",
+    },
+    ChordDefinition {
+        name: "diminished seventh",
+        degrees: "1 b3 b5 bb7",
+        optional: "",
+        characteristic: "b3 b5 bb7",
+    },
+    ChordDefinition {
+        name: "half-diminished seventh",
+        degrees: "1 b3 b5 b7",
+        optional: "",
+        characteristic: "b3 b5 b7",
+    },
+    ChordDefinition {
+        name: "augmented seventh",
+        degrees: "1 3 #5 b7",
+        optional: "",
+        characteristic: "3 #5 b7",
+    },
+    ChordDefinition {
+        name: "augmented major seventh",
+        degrees: "1 3 #5 7",
+        optional: "",
+        characteristic: "3 #5 7",
+    },
+    ChordDefinition {
+        name: "dominant seventh suspended fourth",
+        degrees: "1 4 5 b7",
+        optional: "5",
+        characteristic: "4 b7",
+    },
+    ChordDefinition {
+        name: "added ninth",
+        degrees: "1 3 5 9",
+        optional: "5",
+        characteristic: "3 9",
+    },
+    ChordDefinition {
+        name: "minor added ninth",
+        degrees: "1 b3 5 9",
+        optional: "5",
+        characteristic: "b3 9",
+    },
+    ChordDefinition {
+        name: "dominant ninth",
+        degrees: "1 3 5 b7 9",
+        optional: "5",
+        characteristic: "3 b7 9",
+    },
+    ChordDefinition {
+        name: "major ninth",
+        degrees: "1 3 5 7 9",
+        optional: "5",
+        characteristic: "3 7 9",
+    },
+    ChordDefinition {
+        name: "minor ninth",
+        degrees: "1 b3 5 b7 9",
+        optional: "5",
+        characteristic: "b3 b7 9",
+    },
+    ChordDefinition {
+        name: "dominant ninth suspended fourth",
+        degrees: "1 4 5 b7 9",
+        optional: "5",
+        characteristic: "4 b7 9",
+    },
+    ChordDefinition {
+        name: "dominant eleventh",
+        degrees: "1 3 5 b7 9 11",
+        optional: "3 5 9",
+        characteristic: "b7 11",
+    },
+    ChordDefinition {
+        name: "minor eleventh",
+        degrees: "1 b3 5 b7 9 11",
+        optional: "5 9",
+        characteristic: "b3 b7 11",
+    },
+    ChordDefinition {
+        name: "major seventh sharp eleventh",
+        degrees: "1 3 5 7 #11",
+        optional: "5",
+        characteristic: "3 7 #11",
+    },
+    ChordDefinition {
+        name: "dominant thirteenth",
+        degrees: "1 3 5 b7 9 13",
+        optional: "5 9",
+        characteristic: "3 b7 13",
+    },
+    ChordDefinition {
+        name: "major thirteenth",
+        degrees: "1 3 5 7 9 13",
+        optional: "5 9",
+        characteristic: "3 7 13",
+    },
+    ChordDefinition {
+        name: "minor thirteenth",
+        degrees: "1 b3 5 b7 9 11 13",
+        optional: "5 9 11",
+        characteristic: "b3 b7 13",
+    },
+    ChordDefinition {
+        name: "dominant thirteenth suspended fourth",
+        degrees: "1 4 5 b7 9 13",
+        optional: "5 9",
+        characteristic: "4 b7 13",
+    },
+    ChordDefinition {
+        name: "dominant seventh flat ninth",
+        degrees: "1 3 5 b7 b9",
+        optional: "5",
+        characteristic: "3 b7 b9",
+    },
+    ChordDefinition {
+        name: "dominant seventh sharp ninth",
+        degrees: "1 3 5 b7 #9",
+        optional: "5",
+        characteristic: "3 b7 #9",
+    },
+    ChordDefinition {
+        name: "dominant seventh sharp eleventh",
+        degrees: "1 3 5 b7 #11",
+        optional: "5",
+        characteristic: "3 b7 #11",
+    },
+    ChordDefinition {
+        name: "dominant seventh flat thirteenth",
+        degrees: "1 3 5 b7 b13",
+        optional: "5",
+        characteristic: "3 b7 b13",
+    },
+    ChordDefinition {
+        name: "altered dominant",
+        degrees: "1 3 b7 b9 #9 #11 b13",
+        optional: "b9 #9 #11 b13",
+        characteristic: "3 b7",
+    },
+];
+
+impl ChordDefinition {
+    pub fn quality(&self) -> ChordQuality {
+        ChordQuality::from_degrees(self.degrees).expect("registry degrees must parse")
+    }
+
+    pub fn optional(&self) -> Vec<Degree> {
+        degree::parse_degrees(self.optional).expect("registry degrees must parse")
+    }
+
+    pub fn characteristic(&self) -> Vec<Degree> {
+        degree::parse_degrees(self.characteristic).expect("registry degrees must parse")
+    }
+
+    pub fn symbol(&self, style: SymbolStyle) -> String {
+        self.quality().symbol(style)
+    }
+}
+
+/// Looks up a definition by name, ignoring case, or by chord symbol without
+/// its root, e.g. "half-diminished seventh", "m7b5" or "ø7".
+pub fn find(name: &str) -> Option<&'static ChordDefinition> {
+    DEFINITIONS
+        .iter()
+        .find(|definition| definition.name.eq_ignore_ascii_case(name.trim()))
+        .or_else(|| {
+            let symbol = format!("C{}", name.trim()).parse::<ChordSymbol>().ok()?;
+            lookup(&symbol.quality)
+        })
+}
+
+/// The definition with exactly these chord tones, in any order.
+pub fn lookup(quality: &ChordQuality) -> Option<&'static ChordDefinition> {
+    let mut degrees = quality.degrees().to_vec();
+    degrees.sort_by_key(|d| (d.number(), d.semitones()));
+
+    DEFINITIONS.iter().find(|definition| {
+        let mut other = definition.quality().degrees().to_vec();
+        other.sort_by_key(|d| (d.number(), d.semitones()));
+        other == degrees
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn degree(s: &str) -> Degree {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_definitions() {
+        for definition in DEFINITIONS {
+            let quality = definition.quality();
+            assert!(std::ptr::eq(lookup(&quality).unwrap(), definition));
+            assert!(std::ptr::eq(find(definition.name).unwrap(), definition));
+
+            for &style in [SymbolStyle::Plain, SymbolStyle::Jazz].iter() {
+                let symbol = definition.symbol(style);
+                assert!(
+                    std::ptr::eq(find(&symbol).unwrap(), definition),
+                    "{} {:?}",
+                    definition.name,
+                    symbol
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_optional_and_characteristic() {
+        for definition in DEFINITIONS {
+            let degrees = definition.quality().degrees().to_vec();
+            let optional = definition.optional();
+            let characteristic = definition.characteristic();
+
+            assert!(!characteristic.is_empty(), "{}", definition.name);
+            for degree in optional.iter().chain(characteristic.iter()) {
+                assert!(degrees.contains(degree), "{} {}", definition.name, degree);
+                assert_ne!(degree.number(), 1, "{}", definition.name);
+            }
+            for degree in optional.iter() {
+                assert!(
+                    !characteristic.contains(degree),
+                    "{} {}",
+                    definition.name,
+                    degree
+                );
+            }
+        }
+
+        let dominant = find("7").unwrap();
+        assert_eq!(dominant.optional(), [degree("5")]);
+        assert_eq!(dominant.characteristic(), [degree("3"), degree("b7")]);
+        assert_eq!(
+            find("7alt").unwrap().characteristic(),
+            [degree("3"), degree("b7")]
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("m7b5").unwrap().name, "half-diminished seventh");
+        assert_eq!(find("ø").unwrap().name, "half-diminished seventh");
+        assert_eq!(find("Dominant Ninth").unwrap().degrees, "1 3 5 b7 9");
+        assert_eq!(find("").unwrap().name, "major");
+        assert_eq!(find("°7").unwrap().name, "diminished seventh");
+        assert_eq!(
+            find("7(#11)").unwrap().name,
+            "dominant seventh sharp eleventh"
+        );
+        assert!(find("7(b9,#11)").is_none());
+        assert!(find("nonsense").is_none());
+    }
+}
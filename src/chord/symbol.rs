@@ -0,0 +1,745 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::accidental::Accidental;
+use crate::chord::quality::ChordQuality;
+use crate::degree::{double_flat, flat, natural, sharp, Degree};
+use crate::note::parse::Notation;
+use crate::note::Note;
+
+/// How to write chord symbols.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SymbolStyle {
+    /// Plain text: "Cmaj7", "Cm7b5", "Cdim7", "Caug", "C7(#9)".
+    #[default]
+    Plain,
+    /// Symbols as seen on jazz charts: "CΔ7", "C-7", "Cø7", "C°7", "C+",
+    /// "C7(♯9)".
+    Jazz,
+}
+
+/// A lead-sheet chord symbol: a root, a quality and optionally a different
+/// bass note, e.g. "Ab/C".
+#[derive(fmt::Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ChordSymbol {
+    pub root: Note,
+    pub quality: ChordQuality,
+    pub bass: Option<Note>,
+}
+
+impl ChordSymbol {
+    pub fn new(root: Note, quality: ChordQuality) -> Self {
+        ChordSymbol {
+            root,
+            quality,
+            bass: None,
+        }
+    }
+
+    pub fn with_bass(self, bass: Note) -> Self {
+        ChordSymbol {
+            bass: Some(bass),
+            ..self
+        }
+    }
+
+    pub fn format(&self, style: SymbolStyle) -> String {
+        let mut result = format_note(self.root, style);
+        result.push_str(&self.quality.symbol(style));
+        if let Some(bass) = self.bass {
+            result.push('/');
+            result.push_str(&format_note(bass, style));
+        }
+        result
+    }
+
+    /// Spells the chord from its root, with the bass note first if there is
+    /// one.
+    pub fn spell(&self) -> Vec<Note> {
+        let notes = self.quality.spell(self.root);
+        match self.bass {
+            Some(bass) => Some(bass)
+                .into_iter()
+                .chain(notes.into_iter().filter(|&note| note != bass))
+                .collect(),
+            None => notes,
+        }
+    }
+}
+
+fn format_note(note: Note, style: SymbolStyle) -> String {
+    match style {
+        SymbolStyle::Plain => note.to_string(),
+        SymbolStyle::Jazz => jazz_accidentals(&note.to_string()),
+    }
+}
+
+fn jazz_accidentals(s: &str) -> String {
+    s.replace('b', "♭").replace('#', "♯")
+}
+
+// The tensions of an altered dominant, "7alt".
+const ALTERED: [Degree; 4] = [flat(9), sharp(9), sharp(11), flat(13)];
+
+fn take(degrees: &mut Vec<Degree>, number: u8) -> Option<Degree> {
+    let index = degrees.iter().position(|d| d.number() == number)?;
+    Some(degrees.remove(index))
+}
+
+fn take_natural(degrees: &mut Vec<Degree>, number: u8) -> bool {
+    match degrees
+        .iter()
+        .position(|d| d.number() == number && d.accidental() == Accidental::Natural)
+    {
+        Some(index) => {
+            degrees.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+impl ChordQuality {
+    /// The chord symbol for this quality without its root, e.g. "m7b5" or
+    /// "ø7". A major triad is the empty string.
+    pub fn symbol(&self, style: SymbolStyle) -> String {
+        let jazz = style == SymbolStyle::Jazz;
+        let is = |d: Option<Degree>, degree: Degree| d == Some(degree);
+
+        let mut rest: Vec<Degree> = self
+            .degrees()
+            .iter()
+            .cloned()
+            .filter(|&d| d != natural(1))
+            .collect();
+        rest.sort_by_key(|d| (d.number(), d.semitones()));
+
+        let third = take(&mut rest, 3);
+        let fifth = take(&mut rest, 5);
+        let seventh = take(&mut rest, 7);
+        let sixth = if seventh.is_none() {
+            take(&mut rest, 6)
+        } else {
+            None
+        };
+        let sus = if third.is_none() {
+            take(&mut rest, 4).or_else(|| take(&mut rest, 2))
+        } else {
+            None
+        };
+
+        if is(third, natural(3)) && fifth.is_none() && is(seventh, flat(7)) && rest == ALTERED {
+            return "7alt".to_string();
+        }
+
+        let minor = is(third, flat(3));
+        let mut head = String::new();
+        let mut items: Vec<String> = Vec::new();
+
+        if minor && is(fifth, flat(5)) && is(seventh, double_flat(7)) {
+            head.push_str(if jazz { "°7" } else { "dim7" });
+        } else if minor && is(fifth, flat(5)) && is(seventh, flat(7)) {
+            head.push_str(if jazz { "ø7" } else { "m7b5" });
+        } else if minor && is(fifth, flat(5)) && seventh.is_none() && sixth.is_none() {
+            head.push_str(if jazz { "°" } else { "dim" });
+        } else if is(third, natural(3))
+            && is(fifth, sharp(5))
+            && seventh.is_none()
+            && sixth.is_none()
+        {
+            head.push_str(if jazz { "+" } else { "aug" });
+        } else {
+            if minor {
+                head.push_str(if jazz { "-" } else { "m" });
+            } else if let Some(third) = third.filter(|&d| d != natural(3)) {
+                items.push(third.to_string());
+            }
+
+            if let Some(seventh) = seventh {
+                // Name the chord after its highest stacked extension, so that
+                // 9 and 13 with a seventh make a thirteenth chord. As when
+                // parsing, only a minor thirteenth includes the eleventh.
+                let mut number = 7;
+                if take_natural(&mut rest, 9) {
+                    number = 9;
+                    let eleventh = rest.contains(&natural(11));
+                    if (eleventh || !minor) && take_natural(&mut rest, 13) {
+                        number = 13;
+                        if minor {
+                            take_natural(&mut rest, 11);
+                        }
+                    } else if take_natural(&mut rest, 11) {
+                        number = 11;
+                    }
+                }
+
+                let major = if jazz { "Δ" } else { "maj" };
+                match seventh.accidental() {
+                    Accidental::Natural if minor => items.insert(0, format!("{}{}", major, number)),
+                    Accidental::Natural => head.push_str(&format!("{}{}", major, number)),
+                    Accidental::Flat => head.push_str(&number.to_string()),
+                    _ => items.push(seventh.to_string()),
+                }
+            } else if let Some(sixth) = sixth {
+                if sixth == natural(6) {
+                    head.push('6');
+                    if take_natural(&mut rest, 9) {
+                        head.push_str("/9");
+                    }
+                } else {
+                    items.push(sixth.to_string());
+                }
+            }
+
+            if let Some(sus) = sus {
+                head.push_str(&format!("sus{}", sus));
+            } else if third.is_none() {
+                if is(fifth, natural(5)) && head.is_empty() && rest.is_empty() {
+                    return "5".to_string();
+                }
+                items.push("no3".to_string());
+            }
+
+            match fifth {
+                None => items.push("no5".to_string()),
+                Some(fifth) if fifth != natural(5) => items.push(fifth.to_string()),
+                _ => {}
+            }
+        }
+
+        for d in rest {
+            if d.accidental() == Accidental::Natural {
+                head.push_str(&format!("add{}", d));
+            } else {
+                items.push(d.to_string());
+            }
+        }
+
+        if !items.is_empty() {
+            head.push_str(&format!("({})", items.join(",")));
+        }
+
+        if jazz {
+            jazz_accidentals(&head)
+        } else {
+            head
+        }
+    }
+}
+
+impl fmt::Display for ChordSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let style = if f.alternate() {
+            SymbolStyle::Jazz
+        } else {
+            SymbolStyle::Plain
+        };
+        write!(f, "{}", self.format(style))
+    }
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseChordErrorKind {
+    Empty,
+    InvalidRoot,
+    InvalidBass,
+    UnrecognizedSymbol,
+    MissingDegree,
+}
+
+/// Why a chord symbol couldn't be parsed, and the character position at which
+/// the problem starts.
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct ParseChordError {
+    pub kind: ParseChordErrorKind,
+    pub position: usize,
+    pub input: String,
+}
+
+impl fmt::Display for ParseChordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ParseChordErrorKind::*;
+
+        if self.kind == Empty {
+            return write!(f, "empty chord symbol");
+        }
+
+        let description = match self.kind {
+            Empty => unreachable!(),
+            InvalidRoot => "unrecognized root",
+            InvalidBass => "unrecognized bass note",
+            UnrecognizedSymbol => "unrecognized chord symbol",
+            MissingDegree => "expected a degree",
+        };
+        write!(
+            f,
+            "{} at position {} in {:?}",
+            description, self.position, self.input
+        )
+    }
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+enum Token {
+    Major,
+    Delta,
+    Minor,
+    Diminished,
+    HalfDiminished,
+    Augmented,
+    Suspended,
+    Add,
+    Altered,
+    Omit,
+    SixNine,
+    Number(u8),
+    Degree(Degree),
+}
+
+// Longer keywords come before their prefixes.
+const KEYWORDS: [(&str, Token); 25] = [
+    ("6/9", Token::SixNine),
+    ("69", Token::SixNine),
+    ("maj", Token::Major),
+    ("Maj", Token::Major),
+    ("MAJ", Token::Major),
+    ("M", Token::Major),
+    ("Δ", Token::Delta),
+    ("^", Token::Delta),
+    ("min", Token::Minor),
+    ("mi", Token::Minor),
+    ("m", Token::Minor),
+    ("-", Token::Minor),
+    ("−", Token::Minor),
+    ("dim", Token::Diminished),
+    ("°", Token::Diminished),
+    ("omit", Token::Omit),
+    ("o", Token::Diminished),
+    ("ø", Token::HalfDiminished),
+    ("Ø", Token::HalfDiminished),
+    ("aug", Token::Augmented),
+    ("+", Token::Augmented),
+    ("sus", Token::Suspended),
+    ("add", Token::Add),
+    ("alt", Token::Altered),
+    ("no", Token::Omit),
+];
+
+fn is_accidental(c: char) -> bool {
+    matches!(c, '#' | 'b' | '♯' | '♭' | '𝄪' | '𝄫')
+}
+
+fn tokenize(
+    chars: &[char],
+    offset: usize,
+    err: &impl Fn(ParseChordErrorKind, usize) -> ParseChordError,
+) -> Result<Vec<(Token, usize)>, ParseChordError> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let c = chars[position];
+        let start = position;
+        let seen_number = tokens
+            .iter()
+            .any(|(token, _)| matches!(token, Token::Number(_) | Token::SixNine));
+        let followed_by_digit = chars.get(position + 1).is_some_and(char::is_ascii_digit);
+
+        if c.is_whitespace() || matches!(c, '(' | ')' | ',' | '/') {
+            position += 1;
+            continue;
+        }
+
+        // "+5" and "-9" are alterations once the chord's number is known.
+        if matches!(c, '+' | '-' | '−') && followed_by_digit && seen_number || is_accidental(c) {
+            let mut degree = String::new();
+            while position < chars.len() && is_accidental(chars[position]) {
+                degree.push(chars[position]);
+                position += 1;
+            }
+            if degree.is_empty() {
+                degree.push(if c == '+' { '#' } else { 'b' });
+                position += 1;
+            }
+            while position < chars.len() && chars[position].is_ascii_digit() {
+                degree.push(chars[position]);
+                position += 1;
+            }
+            let degree = degree
+                .parse()
+                .map_err(|_| err(ParseChordErrorKind::UnrecognizedSymbol, offset + start))?;
+            tokens.push((Token::Degree(degree), offset + start));
+            continue;
+        }
+
+        let keyword = KEYWORDS.iter().find(|(keyword, _)| {
+            let keyword: Vec<char> = keyword.chars().collect();
+            chars[position..].starts_with(&keyword)
+        });
+        if let Some(&(keyword, token)) = keyword {
+            tokens.push((token, offset + start));
+            position += keyword.chars().count();
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            while position < chars.len() && chars[position].is_ascii_digit() {
+                position += 1;
+            }
+            let number: String = chars[start..position].iter().collect();
+            let number = number
+                .parse()
+                .map_err(|_| err(ParseChordErrorKind::UnrecognizedSymbol, offset + start))?;
+            tokens.push((Token::Number(number), offset + start));
+            continue;
+        }
+
+        return Err(err(ParseChordErrorKind::UnrecognizedSymbol, offset + start));
+    }
+
+    Ok(tokens)
+}
+
+fn parse_quality(
+    chars: &[char],
+    offset: usize,
+    err: &impl Fn(ParseChordErrorKind, usize) -> ParseChordError,
+) -> Result<ChordQuality, ParseChordError> {
+    let tokens = tokenize(chars, offset, err)?;
+
+    let mut third = Some(natural(3));
+    let mut fifth = Some(natural(5));
+    let mut sixth = None;
+    let mut seventh = None;
+    let mut extensions: Vec<Degree> = Vec::new();
+    let mut major = false;
+    let mut delta = false;
+    let mut diminished = false;
+    let mut seen_number = false;
+
+    let alter = |extensions: &mut Vec<Degree>, d: Degree| {
+        extensions.retain(|&e| e != natural(d.number()));
+        extensions.push(d);
+    };
+
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some((token, position)) = tokens.next() {
+        match token {
+            Token::Major => major = true,
+            Token::Delta => {
+                major = true;
+                delta = true;
+            }
+            Token::Minor => third = Some(flat(3)),
+            Token::Diminished => {
+                third = Some(flat(3));
+                fifth = Some(flat(5));
+                diminished = true;
+            }
+            Token::HalfDiminished => {
+                third = Some(flat(3));
+                fifth = Some(flat(5));
+                seventh = Some(flat(7));
+            }
+            Token::Augmented => fifth = Some(sharp(5)),
+            Token::Suspended => {
+                third = None;
+                let sus = match tokens.peek() {
+                    Some(&(Token::Number(n), _)) if n == 2 || n == 4 => natural(n),
+                    Some(&(Token::Degree(d), _)) if d.number() == 2 || d.number() == 4 => d,
+                    _ => {
+                        extensions.push(natural(4));
+                        continue;
+                    }
+                };
+                tokens.next();
+                extensions.push(sus);
+            }
+            Token::Add => match tokens.next() {
+                Some((Token::Number(n), _)) if n > 1 => extensions.push(natural(n)),
+                Some((Token::Degree(d), _)) if d.number() > 1 => alter(&mut extensions, d),
+                _ => return Err(err(ParseChordErrorKind::MissingDegree, position)),
+            },
+            Token::Omit => match tokens.next() {
+                Some((Token::Number(3), _)) => third = None,
+                Some((Token::Number(5), _)) => fifth = None,
+                _ => return Err(err(ParseChordErrorKind::MissingDegree, position)),
+            },
+            Token::Altered => {
+                fifth = None;
+                seventh.get_or_insert(flat(7));
+                for &d in ALTERED.iter() {
+                    alter(&mut extensions, d);
+                }
+            }
+            Token::SixNine => {
+                seen_number = true;
+                sixth = Some(natural(6));
+                extensions.push(natural(9));
+            }
+            Token::Number(n) if !seen_number => {
+                seen_number = true;
+                match n {
+                    2 | 4 => extensions.push(natural(n)),
+                    5 => third = None,
+                    6 => sixth = Some(natural(6)),
+                    7 | 9 | 11 | 13 => {
+                        seventh = Some(if diminished {
+                            double_flat(7)
+                        } else if major {
+                            natural(7)
+                        } else {
+                            seventh.unwrap_or_else(|| flat(7))
+                        });
+                        let minor = third == Some(flat(3));
+                        for &number in [9, 11, 13].iter() {
+                            if number <= n && (number != 11 || n == 11 || minor) {
+                                extensions.push(natural(number));
+                            }
+                        }
+                    }
+                    _ => return Err(err(ParseChordErrorKind::UnrecognizedSymbol, position)),
+                }
+            }
+            Token::Number(n) if n > 1 => extensions.push(natural(n)),
+            Token::Number(_) => return Err(err(ParseChordErrorKind::UnrecognizedSymbol, position)),
+            Token::Degree(d) => match d.number() {
+                3 => third = Some(d),
+                5 => fifth = Some(d),
+                7 => seventh = Some(d),
+                _ => alter(&mut extensions, d),
+            },
+        }
+    }
+
+    if delta && seventh.is_none() && sixth.is_none() {
+        seventh = Some(natural(7));
+    }
+
+    let mut degrees: Vec<Degree> = Some(natural(1))
+        .into_iter()
+        .chain(third)
+        .chain(fifth)
+        .chain(sixth)
+        .chain(seventh)
+        .chain(extensions)
+        .collect();
+    degrees.sort_by_key(|d| (d.number(), d.semitones()));
+    degrees.dedup();
+
+    Ok(ChordQuality::new(degrees))
+}
+
+impl FromStr for ChordSymbol {
+    type Err = ParseChordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let err = |kind, position| ParseChordError {
+            kind,
+            position,
+            input: s.to_string(),
+        };
+
+        if chars.is_empty() {
+            return Err(err(ParseChordErrorKind::Empty, 0));
+        }
+
+        let root_len = 1 + chars[1..].iter().take_while(|&&c| is_accidental(c)).count();
+        let root: String = chars[..root_len].iter().collect();
+        let root = Note::parse_with(&root, Notation::English)
+            .map_err(|e| err(ParseChordErrorKind::InvalidRoot, e.position))?;
+
+        // A slash followed by a digit belongs to the quality, as in "6/9".
+        let slash = chars
+            .iter()
+            .rposition(|&c| c == '/')
+            .filter(|&i| !chars.get(i + 1).is_some_and(char::is_ascii_digit));
+        let (end, bass) = match slash {
+            Some(i) => {
+                let bass: String = chars[i + 1..].iter().collect();
+                let bass = Note::parse_with(&bass, Notation::English)
+                    .map_err(|e| err(ParseChordErrorKind::InvalidBass, i + 1 + e.position))?;
+                (i, Some(bass))
+            }
+            None => (chars.len(), None),
+        };
+
+        let quality = parse_quality(&chars[root_len..end], root_len, &err)?;
+
+        Ok(ChordSymbol {
+            root,
+            quality,
+            bass,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(s: &str) -> ChordSymbol {
+        s.parse().unwrap()
+    }
+
+    fn quality(degrees: &str) -> ChordQuality {
+        ChordQuality::from_degrees(degrees).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let expected = [
+            ("Cmaj7", "C", "1 3 5 7"),
+            ("F#m7b5", "F#", "1 b3 b5 b7"),
+            ("Bb7(#9,b13)", "Bb", "1 3 5 b7 #9 b13"),
+            ("Gsus4", "G", "1 4 5"),
+            ("Dm(maj7)", "D", "1 b3 5 7"),
+            ("E7alt", "E", "1 3 b7 b9 #9 #11 b13"),
+            ("C6/9", "C", "1 3 5 6 9"),
+            ("Ab/C", "Ab", "1 3 5"),
+            ("C°7", "C", "1 b3 b5 bb7"),
+            ("Cø7", "C", "1 b3 b5 b7"),
+            ("CΔ", "C", "1 3 5 7"),
+            ("C-7", "C", "1 b3 5 b7"),
+            ("Cmi7", "C", "1 b3 5 b7"),
+            ("Cm", "C", "1 b3 5"),
+            ("CM7", "C", "1 3 5 7"),
+            ("Cdim", "C", "1 b3 b5"),
+            ("Co7", "C", "1 b3 b5 bb7"),
+            ("C+", "C", "1 3 #5"),
+            ("Caug7", "C", "1 3 #5 b7"),
+            ("C7+5", "C", "1 3 #5 b7"),
+            ("C7-9", "C", "1 3 5 b7 b9"),
+            ("C7♭9", "C", "1 3 5 b7 b9"),
+            ("C9", "C", "1 3 5 b7 9"),
+            ("C11", "C", "1 3 5 b7 9 11"),
+            ("C13", "C", "1 3 5 b7 9 13"),
+            ("Cm11", "C", "1 b3 5 b7 9 11"),
+            ("Cmaj9(#11)", "C", "1 3 5 7 9 #11"),
+            ("C9sus4", "C", "1 4 5 b7 9"),
+            ("Csus2", "C", "1 2 5"),
+            ("Csus", "C", "1 4 5"),
+            ("Cadd9", "C", "1 3 5 9"),
+            ("Cmadd9", "C", "1 b3 5 9"),
+            ("C5", "C", "1 5"),
+            ("C7(no5)", "C", "1 3 b7"),
+            ("C69", "C", "1 3 5 6 9"),
+            ("Cm6", "C", "1 b3 5 6"),
+        ];
+
+        for &(s, root, degrees) in expected.iter() {
+            let chord = chord(s);
+            assert_eq!(chord.root, root.parse().unwrap(), "{}", s);
+            assert_eq!(chord.quality, quality(degrees), "{}", s);
+        }
+
+        assert_eq!(chord("Ab/C").bass, Some("C".parse().unwrap()));
+        assert_eq!(chord("C6/9").bass, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseChordErrorKind::*;
+
+        let error = |s: &str| s.parse::<ChordSymbol>().unwrap_err();
+
+        assert_eq!(error("").kind, Empty);
+        assert_eq!(error("H7").kind, InvalidRoot);
+        assert_eq!(error("C7/Q").kind, InvalidBass);
+        assert_eq!(error("C7/Q").position, 3);
+        assert_eq!(error("Cadd").kind, MissingDegree);
+        let e = error("Cm7?");
+        assert_eq!(e.kind, UnrecognizedSymbol);
+        assert_eq!(e.position, 3);
+        assert_eq!(
+            e.to_string(),
+            "unrecognized chord symbol at position 3 in \"Cm7?\""
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let expected = [
+            ("1 3 5", "", ""),
+            ("1 b3 5", "m", "-"),
+            ("1 b3 b5", "dim", "°"),
+            ("1 3 #5", "aug", "+"),
+            ("1 2 5", "sus2", "sus2"),
+            ("1 4 5", "sus4", "sus4"),
+            ("1 b2 5", "susb2", "sus♭2"),
+            ("1 #4 5", "sus#4", "sus♯4"),
+            ("1 5", "5", "5"),
+            ("1 3 5 b7", "7", "7"),
+            ("1 3 5 7", "maj7", "Δ7"),
+            ("1 b3 5 b7", "m7", "-7"),
+            ("1 b3 b5 bb7", "dim7", "°7"),
+            ("1 b3 b5 b7", "m7b5", "ø7"),
+            ("1 3 #5 b7", "7(#5)", "7(♯5)"),
+            ("1 3 #5 7", "maj7(#5)", "Δ7(♯5)"),
+            ("1 b3 5 7", "m(maj7)", "-(Δ7)"),
+            ("1 b3 b5 7", "m(maj7,b5)", "-(Δ7,♭5)"),
+            ("1 3 5 6", "6", "6"),
+            ("1 3 5 6 9", "6/9", "6/9"),
+            ("1 3 5 9", "add9", "add9"),
+            ("1 3 5 b7 9", "9", "9"),
+            ("1 3 5 b7 9 13", "13", "13"),
+            ("1 3 5 b7 9 11 13", "13add11", "13add11"),
+            ("1 b3 5 b7 9 11 13", "m13", "-13"),
+            ("1 b3 5 b7 9 13", "m9add13", "-9add13"),
+            ("1 b3 5 b7 9 11", "m11", "-11"),
+            ("1 3 5 7 9 #11", "maj9(#11)", "Δ9(♯11)"),
+            ("1 4 5 b7 9", "9sus4", "9sus4"),
+            ("1 3 5 b7 #9 b13", "7(#9,b13)", "7(♯9,♭13)"),
+            ("1 3 b7 b9 #9 #11 b13", "7alt", "7alt"),
+            ("1 3 b7", "7(no5)", "7(no5)"),
+        ];
+
+        for &(degrees, plain, jazz) in expected.iter() {
+            let quality = quality(degrees);
+            assert_eq!(quality.symbol(SymbolStyle::Plain), plain, "{}", degrees);
+            assert_eq!(quality.symbol(SymbolStyle::Jazz), jazz, "{}", degrees);
+
+            for &s in [plain, jazz].iter() {
+                assert_eq!(chord(&format!("C{}", s)).quality, quality, "{}", s);
+            }
+        }
+
+        assert_eq!(chord("Bb7(#9,b13)").to_string(), "Bb7(#9,b13)");
+        assert_eq!(format!("{:#}", chord("Bb7(#9,b13)")), "B♭7(♯9,♭13)");
+        assert_eq!(chord("Ab/C").to_string(), "Ab/C");
+        assert_eq!(format!("{:#}", chord("F#m7b5")), "F♯ø7");
+        assert_eq!(format!("{}", quality("1 b3 b5 b7")), "m7b5");
+        assert_eq!(format!("{:#}", quality("1 b3 b5 b7")), "ø7");
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        use crate::chord::registry::DEFINITIONS;
+
+        for definition in DEFINITIONS {
+            let quality = definition.quality();
+            for &style in [SymbolStyle::Plain, SymbolStyle::Jazz].iter() {
+                let symbol = format!("C{}", quality.symbol(style));
+                assert_eq!(chord(&symbol).quality, quality, "{}", symbol);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spell() {
+        let spell = |s: &str| {
+            chord(s)
+                .spell()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        assert_eq!(spell("C°7"), "C Eb Gb Bbb");
+        assert_eq!(spell("Ab/C"), "C Ab Eb");
+        assert_eq!(spell("C/Bb"), "Bb C E G");
+        assert_eq!(spell("Bb7(#9,b13)"), "Bb D F Ab C# Gb");
+    }
+}
@@ -0,0 +1,526 @@
+use std::fmt;
+
+use crate::chord::quality::ChordQuality;
+use crate::degree::Degree;
+use crate::interval::Interval;
+use crate::note::{Note, UnspellableNote};
+use crate::pitch::Pitch;
+
+/// The two common rootless voicings of a seventh chord: the A form has the
+/// third at the bottom (3 5 7 9) and the B form the seventh (7 9 3 5).
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RootlessForm {
+    A,
+    B,
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub enum VoicingError {
+    Unspellable(UnspellableNote),
+    /// The quality has no chord tone with this number, e.g. no seventh for a
+    /// rootless voicing.
+    MissingChordTone(u8),
+    /// The pitch isn't a chord tone and isn't in the bass.
+    NotAChordTone(Pitch),
+    /// The voicing doesn't fit in the requested range.
+    OutOfRange,
+    /// There's nothing to voice: no pitches, or a quality with no chord tones.
+    Empty,
+}
+
+impl From<UnspellableNote> for VoicingError {
+    fn from(e: UnspellableNote) -> Self {
+        VoicingError::Unspellable(e)
+    }
+}
+
+impl fmt::Display for VoicingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VoicingError::Unspellable(e) => write!(f, "{}", e),
+            VoicingError::MissingChordTone(number) => {
+                write!(f, "the chord has no {} to voice", number)
+            }
+            VoicingError::NotAChordTone(pitch) => write!(f, "{} isn't in the chord", pitch),
+            VoicingError::OutOfRange => write!(f, "the chord doesn't fit in the range"),
+            VoicingError::Empty => write!(f, "there's nothing to voice"),
+        }
+    }
+}
+
+/// A chord laid out as pitches, lowest first. Each voice knows which chord
+/// tone it plays; a slash chord's bass may play none.
+#[derive(fmt::Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Voicing {
+    root: Note,
+    quality: ChordQuality,
+    voices: Vec<(Pitch, Option<Degree>)>,
+}
+
+impl Voicing {
+    /// Root position in close position: each chord tone in turn, as close as
+    /// possible above the one before, so that C9 from C4 is C4 E4 G4 Bb4 D5.
+    pub fn close(root: Pitch, quality: &ChordQuality) -> Result<Self, VoicingError> {
+        if quality.degrees().is_empty() {
+            return Err(VoicingError::Empty);
+        }
+
+        let mut voices = Vec::new();
+        let mut floor = None;
+        for &degree in quality.degrees() {
+            let pitch = match floor {
+                None => root.transpose(degree.interval())?,
+                Some(floor) => above(root.note().transpose(degree.interval())?, floor),
+            };
+            voices.push((pitch, Some(degree)));
+            floor = Some(pitch);
+        }
+
+        Ok(Voicing {
+            root: root.note(),
+            quality: quality.clone(),
+            voices,
+        })
+    }
+
+    /// A rootless voicing with its lowest note at or above `low`. The ninth
+    /// stands in for the root, and a thirteenth, if the chord has one, for the
+    /// fifth.
+    pub fn rootless(
+        root: Note,
+        quality: &ChordQuality,
+        form: RootlessForm,
+        low: Pitch,
+    ) -> Result<Self, VoicingError> {
+        let third = quality.degree(3).ok_or(VoicingError::MissingChordTone(3))?;
+        let seventh = quality
+            .degree(7)
+            .or_else(|| quality.degree(6).filter(|d| d.number() == 6))
+            .ok_or(VoicingError::MissingChordTone(7))?;
+        let fifth = quality
+            .degrees()
+            .iter()
+            .find(|d| d.number() == 13)
+            .or_else(|| quality.degrees().iter().find(|d| d.number() == 5))
+            .cloned();
+        let ninth = quality
+            .degrees()
+            .iter()
+            .find(|d| d.number() == 9)
+            .cloned()
+            .unwrap_or_else(|| "9".parse().unwrap());
+
+        let degrees: Vec<Degree> = match form {
+            RootlessForm::A => vec![Some(third), fifth, Some(seventh), Some(ninth)],
+            RootlessForm::B => vec![Some(seventh), Some(ninth), Some(third), fifth],
+        }
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let mut voices: Vec<(Pitch, Option<Degree>)> = Vec::new();
+        for degree in degrees {
+            let note = root.transpose(degree.interval())?;
+            let pitch = match voices.last() {
+                None => at_or_above(note, low),
+                Some(&(floor, _)) => above(note, floor),
+            };
+            voices.push((pitch, Some(degree)));
+        }
+
+        Ok(Voicing {
+            root,
+            quality: quality.clone(),
+            voices,
+        })
+    }
+
+    /// Root position spread over the range from `low` to `high`, with the
+    /// root at the bottom and the other chord tones, in order, as evenly
+    /// spaced as their pitches allow.
+    pub fn spread(
+        root: Note,
+        quality: &ChordQuality,
+        low: Pitch,
+        high: Pitch,
+    ) -> Result<Self, VoicingError> {
+        let degrees = quality.degrees();
+        if degrees.is_empty() {
+            return Err(VoicingError::Empty);
+        }
+        let bass = at_or_above(root.transpose(degrees[0].interval())?, low);
+        let span = (high.semitones() - bass.semitones()) as f64;
+
+        let mut voices = vec![(bass, Some(degrees[0]))];
+        for (i, &degree) in degrees.iter().enumerate().skip(1) {
+            let note = root.transpose(degree.interval())?;
+            let target = bass.semitones() as f64 + span * i as f64 / (degrees.len() - 1) as f64;
+            let floor = voices.last().unwrap().0;
+
+            let lower = above(note, floor);
+            let mut pitch = lower;
+            while (pitch.semitones() as f64) < target - 6.0 {
+                pitch = Pitch(note, pitch.octave() + 1);
+            }
+            voices.push((pitch, Some(degree)));
+        }
+
+        if voices.last().unwrap().0 > high {
+            return Err(VoicingError::OutOfRange);
+        }
+
+        Ok(Voicing {
+            root,
+            quality: quality.clone(),
+            voices,
+        })
+    }
+
+    /// Labels the given pitches with the chord tones they play. Only the
+    /// lowest pitch may fall outside the chord, making a slash chord.
+    pub fn from_pitches(
+        root: Note,
+        quality: &ChordQuality,
+        pitches: impl AsRef<[Pitch]>,
+    ) -> Result<Self, VoicingError> {
+        let mut pitches = pitches.as_ref().to_vec();
+        if pitches.is_empty() {
+            return Err(VoicingError::Empty);
+        }
+        pitches.sort();
+
+        let tones = quality.spell(root);
+        let voices = pitches
+            .iter()
+            .enumerate()
+            .map(|(i, &pitch)| {
+                let degree = tones
+                    .iter()
+                    .position(|tone| tone.is_enharmonic_to(pitch.note()))
+                    .map(|index| quality.degrees()[index]);
+                match degree {
+                    None if i > 0 => Err(VoicingError::NotAChordTone(pitch)),
+                    degree => Ok((pitch, degree)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Voicing {
+            root,
+            quality: quality.clone(),
+            voices,
+        })
+    }
+
+    /// Moves the lowest voice up an octave `times` times, so that one
+    /// inversion of a close C major triad C4 E4 G4 is E4 G4 C5.
+    pub fn inverted(&self, times: usize) -> Self {
+        let mut result = self.clone();
+        for _ in 0..times {
+            let (pitch, degree) = result.voices.remove(0);
+            result
+                .voices
+                .push((Pitch(pitch.note(), pitch.octave() + 1), degree));
+            result.voices.sort_by_key(|&(pitch, _)| pitch);
+        }
+        result
+    }
+
+    /// Moves every other voice, starting from the second lowest, up an
+    /// octave, so that C4 E4 G4 opens out to C4 G4 E5.
+    pub fn open(&self) -> Self {
+        let mut result = self.clone();
+        for (pitch, _) in result.voices.iter_mut().skip(1).step_by(2) {
+            *pitch = Pitch(pitch.note(), pitch.octave() + 1);
+        }
+        result.voices.sort_by_key(|&(pitch, _)| pitch);
+        result
+    }
+
+    /// Drops the given voices, counted from the top starting at 1, down an
+    /// octave.
+    pub fn drop(&self, voices: &[usize]) -> Self {
+        let mut result = self.clone();
+        let len = result.voices.len();
+        for &voice in voices {
+            if (1..=len).contains(&voice) {
+                let (pitch, _) = &mut result.voices[len - voice];
+                *pitch = Pitch(pitch.note(), pitch.octave() - 1);
+            }
+        }
+        result.voices.sort_by_key(|&(pitch, _)| pitch);
+        result
+    }
+
+    pub fn drop_2(&self) -> Self {
+        self.drop(&[2])
+    }
+
+    pub fn drop_3(&self) -> Self {
+        self.drop(&[3])
+    }
+
+    pub fn drop_2_and_4(&self) -> Self {
+        self.drop(&[2, 4])
+    }
+
+    /// Puts `bass` under the chord as a slash chord. If the bass is a chord
+    /// tone, it's taken out of the upper voices.
+    pub fn with_bass(&self, bass: Note) -> Self {
+        let mut result = self.clone();
+        let degree = result
+            .voices
+            .iter()
+            .position(|(pitch, _)| pitch.note().is_enharmonic_to(bass))
+            .map(|index| result.voices.remove(index).1)
+            .unwrap_or(None);
+
+        let lowest = match result.voices.first() {
+            Some(&(pitch, _)) => pitch,
+            None => self.voices[0].0,
+        };
+        let mut pitch = above(bass, Pitch(lowest.note(), lowest.octave() - 1));
+        if pitch >= lowest {
+            pitch = Pitch(bass, pitch.octave() - 1);
+        }
+        result.voices.insert(0, (pitch, degree));
+        result
+    }
+
+    pub fn root(&self) -> Note {
+        self.root
+    }
+
+    pub fn quality(&self) -> &ChordQuality {
+        &self.quality
+    }
+
+    pub fn pitches(&self) -> Vec<Pitch> {
+        self.voices.iter().map(|&(pitch, _)| pitch).collect()
+    }
+
+    /// The chord tone each voice plays, lowest first.
+    pub fn degrees(&self) -> Vec<Option<Degree>> {
+        self.voices.iter().map(|&(_, degree)| degree).collect()
+    }
+
+    pub fn bass(&self) -> Pitch {
+        self.voices[0].0
+    }
+
+    pub fn top(&self) -> Pitch {
+        self.voices[self.voices.len() - 1].0
+    }
+
+    /// The interval from the lowest to the highest voice, or `None` if it's
+    /// too wide to name.
+    pub fn span(&self) -> Option<Interval> {
+        Interval::between_pitches(self.bass(), self.top())
+    }
+
+    /// Which chord tone is in the bass, counting the root as 0, or `None` if
+    /// it's an extension or not in the chord.
+    pub fn inversion(&self) -> Option<usize> {
+        let degree = self.voices[0].1.filter(|d| d.number() <= 7)?;
+        self.quality.degrees().iter().position(|&d| d == degree)
+    }
+
+    pub fn is_rootless(&self) -> bool {
+        !self
+            .voices
+            .iter()
+            .any(|&(_, degree)| degree.map(|d| d.number()) == Some(1))
+    }
+
+    pub fn is_slash(&self) -> bool {
+        self.voices[0].1.is_none()
+    }
+
+    /// Whether no voice's note could fit between two neighbouring voices.
+    pub fn is_close(&self) -> bool {
+        self.voices.windows(2).all(|pair| {
+            let (low, high) = (pair[0].0, pair[1].0);
+            self.voices.iter().all(|&(pitch, _)| {
+                let candidate = above(pitch.note(), low);
+                candidate >= high
+            })
+        })
+    }
+}
+
+// The lowest pitch of `note` strictly above `floor`.
+pub(crate) fn above(note: Note, floor: Pitch) -> Pitch {
+    let mut pitch = Pitch(note, floor.octave() - 1);
+    while pitch <= floor {
+        pitch = Pitch(note, pitch.octave() + 1);
+    }
+    pitch
+}
+
+fn at_or_above(note: Note, floor: Pitch) -> Pitch {
+    above(note, Pitch::from_semitones(floor.semitones() - 1))
+}
+
+impl fmt::Display for Voicing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pitches: Vec<String> = self.pitches().iter().map(ToString::to_string).collect();
+        write!(f, "{}", pitches.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chord::quality::{ninths, sevenths, thirteenths, triads};
+
+    fn pitch(s: &str) -> Pitch {
+        s.parse().unwrap()
+    }
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_close_and_inversions() {
+        let c = Voicing::close(pitch("C4"), &triads::MAJOR).unwrap();
+        assert_eq!(c.to_string(), "C4 E4 G4");
+        assert_eq!(c.inversion(), Some(0));
+        assert!(c.is_close());
+
+        let first = c.inverted(1);
+        assert_eq!(first.to_string(), "E4 G4 C5");
+        assert_eq!(first.inversion(), Some(1));
+        assert_eq!(first.bass(), pitch("E4"));
+        assert_eq!(first.top(), pitch("C5"));
+        assert_eq!(c.inverted(2).inversion(), Some(2));
+        assert_eq!(c.inverted(3).to_string(), "C5 E5 G5");
+
+        let c9 = Voicing::close(pitch("C4"), &ninths::DOMINANT).unwrap();
+        assert_eq!(c9.to_string(), "C4 E4 G4 Bb4 D5");
+        assert_eq!(c9.span().unwrap().to_string(), "M9");
+
+        let dim = Voicing::close(pitch("B3"), &sevenths::DIMINISHED).unwrap();
+        assert_eq!(dim.to_string(), "B3 D4 F4 Ab4");
+        assert_eq!(
+            Voicing::close(pitch("Cb4"), &sevenths::DIMINISHED).unwrap_err(),
+            VoicingError::Unspellable(UnspellableNote {
+                note_name: crate::note_name::NoteName::B,
+                pitch: note("Ab").pitch(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_open_and_drop() {
+        let c = Voicing::close(pitch("C4"), &triads::MAJOR).unwrap();
+        let open = c.open();
+        assert_eq!(open.to_string(), "C4 G4 E5");
+        assert!(!open.is_close());
+        assert_eq!(open.inversion(), Some(0));
+
+        let cmaj7 = Voicing::close(pitch("C4"), &sevenths::MAJOR).unwrap();
+        assert_eq!(cmaj7.drop_2().to_string(), "G3 C4 E4 B4");
+        assert_eq!(cmaj7.drop_2().inversion(), Some(2));
+        assert_eq!(cmaj7.drop_3().to_string(), "E3 C4 G4 B4");
+        assert_eq!(cmaj7.drop_2_and_4().to_string(), "C3 G3 E4 B4");
+        assert_eq!(cmaj7.drop_2_and_4().top(), pitch("B4"));
+        assert_eq!(cmaj7.inverted(1).drop_2().to_string(), "B3 E4 G4 C5");
+    }
+
+    #[test]
+    fn test_rootless() {
+        let a = Voicing::rootless(note("D"), &ninths::MINOR, RootlessForm::A, pitch("E3")).unwrap();
+        assert_eq!(a.to_string(), "F3 A3 C4 E4");
+        assert!(a.is_rootless());
+        assert_eq!(a.inversion(), Some(1));
+
+        let b = Voicing::rootless(note("D"), &ninths::MINOR, RootlessForm::B, pitch("C3")).unwrap();
+        assert_eq!(b.to_string(), "C3 E3 F3 A3");
+
+        let g13 = Voicing::rootless(
+            note("G"),
+            &thirteenths::DOMINANT,
+            RootlessForm::B,
+            pitch("E3"),
+        )
+        .unwrap();
+        assert_eq!(g13.to_string(), "F3 A3 B3 E4");
+
+        // Seventh chords without a ninth borrow a natural one.
+        let cmaj7 =
+            Voicing::rootless(note("C"), &sevenths::MAJOR, RootlessForm::A, pitch("C4")).unwrap();
+        assert_eq!(cmaj7.to_string(), "E4 G4 B4 D5");
+
+        assert_eq!(
+            Voicing::rootless(
+                note("C"),
+                &triads::SUSPENDED_4,
+                RootlessForm::A,
+                pitch("C4")
+            )
+            .unwrap_err(),
+            VoicingError::MissingChordTone(3)
+        );
+    }
+
+    #[test]
+    fn test_spread() {
+        let spread =
+            Voicing::spread(note("C"), &sevenths::MAJOR, pitch("C2"), pitch("C5")).unwrap();
+        assert_eq!(spread.to_string(), "C2 E3 G3 B4");
+        assert_eq!(spread.inversion(), Some(0));
+        assert!(!spread.is_close());
+
+        assert_eq!(
+            Voicing::spread(note("C"), &ninths::DOMINANT, pitch("C4"), pitch("E4")).unwrap_err(),
+            VoicingError::OutOfRange
+        );
+    }
+
+    #[test]
+    fn test_slash_chords() {
+        let c = Voicing::close(pitch("C4"), &triads::MAJOR).unwrap();
+
+        let over_d = c.with_bass(note("D"));
+        assert_eq!(over_d.to_string(), "D3 C4 E4 G4");
+        assert!(over_d.is_slash());
+        assert_eq!(over_d.inversion(), None);
+
+        let over_e = c.with_bass(note("E"));
+        assert_eq!(over_e.to_string(), "E3 C4 G4");
+        assert_eq!(over_e.inversion(), Some(1));
+        assert!(!over_e.is_slash());
+
+        let labelled = Voicing::from_pitches(
+            note("C"),
+            &triads::MAJOR,
+            [pitch("E4"), pitch("Bb2"), pitch("G3"), pitch("C4")],
+        )
+        .unwrap();
+        assert_eq!(labelled.to_string(), "Bb2 G3 C4 E4");
+        assert!(labelled.is_slash());
+        assert_eq!(
+            Voicing::from_pitches(note("C"), &triads::MAJOR, [pitch("C3"), pitch("D4")])
+                .unwrap_err(),
+            VoicingError::NotAChordTone(pitch("D4"))
+        );
+    }
+
+    #[test]
+    fn test_nothing_to_voice() {
+        let empty = ChordQuality::new([]);
+        assert_eq!(
+            Voicing::from_pitches(note("C"), &triads::MAJOR, []).unwrap_err(),
+            VoicingError::Empty
+        );
+        assert_eq!(
+            Voicing::close(pitch("C4"), &empty).unwrap_err(),
+            VoicingError::Empty
+        );
+        assert_eq!(
+            Voicing::spread(note("C"), &empty, pitch("C2"), pitch("C5")).unwrap_err(),
+            VoicingError::Empty
+        );
+    }
+}
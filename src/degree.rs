@@ -0,0 +1,189 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::accidental::Accidental;
+use crate::interval::quality::IntervalQuality;
+use crate::interval::Interval;
+
+/// A degree above a root written relative to the major scale, e.g. b3 for a
+/// minor third or #11 for an augmented eleventh.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Degree {
+    accidental: Accidental,
+    number: u8,
+}
+
+impl Degree {
+    pub fn new(accidental: Accidental, number: u8) -> Option<Self> {
+        if number == 0 {
+            None
+        } else {
+            Some(Degree { accidental, number })
+        }
+    }
+
+    /// The degree reached by `interval`, if it differs from the major or
+    /// perfect interval of the same number by at most a double accidental.
+    pub fn from_interval(interval: Interval) -> Option<Self> {
+        let offset = interval.semitones() - unaltered(interval.number()).semitones();
+        let accidental = Accidental::from_interval(i8::try_from(offset).ok()?)?;
+        Degree::new(accidental, interval.number())
+    }
+
+    pub const fn accidental(self) -> Accidental {
+        self.accidental
+    }
+
+    pub const fn number(self) -> u8 {
+        self.number
+    }
+
+    pub fn interval(self) -> Interval {
+        let semitones = unaltered(self.number).semitones() + self.accidental.interval() as i16;
+        Interval::from_number_and_semitones(self.number, semitones).unwrap()
+    }
+
+    pub fn semitones(self) -> i16 {
+        self.interval().semitones()
+    }
+}
+
+fn unaltered(number: u8) -> Interval {
+    Interval::new(IntervalQuality::Perfect, number)
+        .or_else(|| Interval::new(IntervalQuality::Major, number))
+        .unwrap()
+}
+
+// Degrees the crate writes down itself, without parsing them at run time.
+
+pub(crate) const fn natural(number: u8) -> Degree {
+    altered(Accidental::Natural, number)
+}
+
+pub(crate) const fn flat(number: u8) -> Degree {
+    altered(Accidental::Flat, number)
+}
+
+pub(crate) const fn sharp(number: u8) -> Degree {
+    altered(Accidental::Sharp, number)
+}
+
+pub(crate) const fn double_flat(number: u8) -> Degree {
+    altered(Accidental::DoubleFlat, number)
+}
+
+const fn altered(accidental: Accidental, number: u8) -> Degree {
+    assert!(number != 0, "degrees are numbered from 1");
+    Degree { accidental, number }
+}
+
+/// Parses a list of degrees separated by spaces or commas, e.g. "1 b3 4 b5 5
+/// b7".
+pub fn parse_degrees(s: &str) -> Result<Vec<Degree>, UnrecognizedDegree> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|degree| !degree.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+impl fmt::Display for Degree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.accidental, self.number)
+    }
+}
+
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct UnrecognizedDegree(String);
+
+impl FromStr for Degree {
+    type Err = UnrecognizedDegree;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || UnrecognizedDegree(s.to_string());
+
+        let split = s.find(|c: char| c.is_ascii_digit()).ok_or_else(err)?;
+        let (accidental, number) = s.split_at(split);
+
+        let mut total = 0i8;
+        for c in accidental.chars() {
+            let step = match c {
+                '#' | '♯' => 1,
+                'b' | '♭' => -1,
+                'x' | '𝄪' => 2,
+                '𝄫' => -2,
+                '♮' if accidental.len() == c.len_utf8() => 0,
+                _ => return Err(err()),
+            };
+            if total != 0 && (total > 0) != (step > 0) {
+                return Err(err());
+            }
+            total = total.saturating_add(step);
+        }
+
+        let accidental = Accidental::from_interval(total).ok_or_else(err)?;
+        let number = number.parse::<u8>().map_err(|_| err())?;
+
+        Degree::new(accidental, number).ok_or_else(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn degree(s: &str) -> Degree {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        use crate::accidental::Accidental::*;
+
+        assert_eq!(degree("3"), Degree::new(Natural, 3).unwrap());
+        assert_eq!(degree("b3"), Degree::new(Flat, 3).unwrap());
+        assert_eq!(degree("♭3"), Degree::new(Flat, 3).unwrap());
+        assert_eq!(degree("#11"), Degree::new(Sharp, 11).unwrap());
+        assert_eq!(degree("bb7"), Degree::new(DoubleFlat, 7).unwrap());
+        assert_eq!(degree("𝄫7"), Degree::new(DoubleFlat, 7).unwrap());
+        assert_eq!(degree("♮6"), Degree::new(Natural, 6).unwrap());
+        assert_eq!(degree("♯4").to_string(), "#4");
+
+        for s in &["", "b", "0", "#b5", "bbb3", "3b", "♮#4", "m3"] {
+            assert!(s.parse::<Degree>().is_err(), "{:?}", s);
+        }
+
+        assert_eq!(
+            parse_degrees("1 b3, 4  b5 5 b7").unwrap(),
+            ["1", "b3", "4", "b5", "5", "b7"]
+                .iter()
+                .map(|s| degree(s))
+                .collect::<Vec<_>>()
+        );
+        assert!(parse_degrees("1 b3 x").is_err());
+    }
+
+    #[test]
+    fn test_intervals() {
+        let expected = [
+            ("1", "P1"),
+            ("b2", "m2"),
+            ("#2", "A2"),
+            ("b3", "m3"),
+            ("#4", "A4"),
+            ("b5", "d5"),
+            ("bb7", "d7"),
+            ("b9", "m9"),
+            ("#11", "A11"),
+            ("13", "M13"),
+        ];
+        for &(d, i) in expected.iter() {
+            let interval: Interval = i.parse().unwrap();
+            assert_eq!(degree(d).interval(), interval, "{}", d);
+            assert_eq!(Degree::from_interval(interval), Some(degree(d)), "{}", d);
+        }
+
+        assert_eq!(Degree::from_interval("ddd5".parse().unwrap()), None);
+        assert_eq!(degree("b5").semitones(), 6);
+    }
+}
@@ -0,0 +1,696 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::accidental::Accidental;
+use crate::interval::Interval;
+use crate::key;
+use crate::note::parse::{ParseNoteError, ParseNoteErrorKind};
+use crate::note::Note;
+use crate::note_name::NoteName;
+use crate::pitch::{Pitch, UnrecognizedPitch};
+use crate::tuning::{self, Tuning, CONCERT_A};
+
+/// An equal division of the octave into `divisions` steps. Notes are placed
+/// by their position on the line of fifths, with each fifth the whole number
+/// of steps closest to a pure 3:2, so that in 19- and 31-EDO, as in
+/// meantone, G# is lower than Ab.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Edo {
+    divisions: u16,
+    fifth: u16,
+}
+
+impl Edo {
+    pub const FIFTY_THREE: Edo = Edo {
+        divisions: 53,
+        fifth: 31,
+    };
+    pub const NINETEEN: Edo = Edo {
+        divisions: 19,
+        fifth: 11,
+    };
+    pub const THIRTY_ONE: Edo = Edo {
+        divisions: 31,
+        fifth: 18,
+    };
+    pub const TWELVE: Edo = Edo {
+        divisions: 12,
+        fifth: 7,
+    };
+    pub const TWENTY_FOUR: Edo = Edo {
+        divisions: 24,
+        fifth: 14,
+    };
+
+    /// The division into `divisions` steps, if a sharp in it raises a note
+    /// by at least one step and every step can be spelled with at most a
+    /// double sharp or flat, a half accidental and three ups or downs. Past
+    /// about a hundred divisions, most can't.
+    pub fn new(divisions: u16) -> Option<Self> {
+        let fifth = (divisions as f64 * (3.0f64 / 2.0).log2()).round() as u16;
+        let edo = Edo { divisions, fifth };
+        if divisions > 0
+            && edo.sharp() > 0
+            && edo.diatonic_semitone() > 0
+            && edo.spells_every_step()
+        {
+            Some(edo)
+        } else {
+            None
+        }
+    }
+
+    pub const fn divisions(self) -> u16 {
+        self.divisions
+    }
+
+    /// The size of a perfect fifth in steps.
+    pub const fn fifth(self) -> u16 {
+        self.fifth
+    }
+
+    /// The steps a sharp raises a note by: the chromatic semitone, as from
+    /// C to C#.
+    pub const fn sharp(self) -> i32 {
+        7 * self.fifth as i32 - 4 * self.divisions as i32
+    }
+
+    /// The steps from E to F.
+    pub const fn diatonic_semitone(self) -> i32 {
+        3 * self.divisions as i32 - 5 * self.fifth as i32
+    }
+
+    /// Whether a sharp is an even number of steps, so it can be halved into
+    /// half-sharps and half-flats.
+    pub const fn has_half_accidentals(self) -> bool {
+        self.sharp() % 2 == 0
+    }
+
+    pub fn step_cents(self) -> f64 {
+        1200.0 / self.divisions as f64
+    }
+
+    /// The size of `interval` in steps.
+    pub fn interval_steps(self, interval: Interval) -> i32 {
+        let steps = interval.number() as i32 - 1;
+        let letter = NoteName::from_steps_above_c(steps as u8);
+        let natural = letter.semitones_above_c() as i32 + 12 * (steps / 7);
+        let sharps = interval.semitones() as i32 - natural;
+
+        self.letter_steps(letter) + steps / 7 * self.divisions as i32 + sharps * self.sharp()
+    }
+
+    // The steps from C up to a natural note in the same octave.
+    fn letter_steps(self, note_name: NoteName) -> i32 {
+        let fifths = key::note_fifths(Note(note_name, Accidental::Natural)) as i32;
+        (fifths * self.fifth as i32).rem_euclid(self.divisions as i32)
+    }
+
+    // The steps that `half_sharps` half-sharps raise a note by, if this
+    // division has them.
+    fn half_sharp_steps(self, half_sharps: i8) -> Option<i32> {
+        if half_sharps % 2 == 0 {
+            Some(half_sharps as i32 / 2 * self.sharp())
+        } else if self.has_half_accidentals() {
+            Some(half_sharps as i32 * self.sharp() / 2)
+        } else {
+            None
+        }
+    }
+
+    /// The simplest spelling of the note `steps` above C, with the octave
+    /// it falls in relative to C's. Spellings with the fewest accidentals
+    /// win, natural letters first and sharps before flats. Ups and downs are
+    /// only used where the smallest accidental is more than one step, or
+    /// where a step can't be reached without them.
+    pub fn spell(self, steps: i32) -> (EdoNote, i32) {
+        let smallest = if self.has_half_accidentals() {
+            self.sharp() / 2
+        } else {
+            self.sharp()
+        };
+        let spelling = if smallest > 1 {
+            self.try_spell(steps, &UPS)
+        } else {
+            self.try_spell(steps, &UPS[..1])
+                .or_else(|| self.try_spell(steps, &UPS))
+        };
+        spelling.expect("`Edo::new` only accepts divisions whose steps can all be spelled")
+    }
+
+    fn spells_every_step(self) -> bool {
+        let divisions = self.divisions as usize;
+        if divisions > 7 * HALF_SHARPS.len() * UPS.len() {
+            return false;
+        }
+
+        let mut spelled = vec![false; divisions];
+        for &ups in &UPS {
+            for &half_sharps in &HALF_SHARPS {
+                for letter in 0..7 {
+                    let note = EdoNote::new(NoteName::from_steps_above_c(letter), half_sharps, ups);
+                    if let Some(steps) = note.steps(self) {
+                        spelled[steps.rem_euclid(divisions as i32) as usize] = true;
+                    }
+                }
+            }
+        }
+        spelled.into_iter().all(|spelled| spelled)
+    }
+
+    fn try_spell(self, steps: i32, ups: &[i8]) -> Option<(EdoNote, i32)> {
+        let divisions = self.divisions as i32;
+        let mut candidates = Vec::new();
+        for &ups in ups {
+            for &half_sharps in &HALF_SHARPS {
+                for letter in 0..7 {
+                    let note = EdoNote {
+                        note_name: NoteName::from_steps_above_c(letter),
+                        half_sharps,
+                        ups,
+                    };
+                    if let Some(note_steps) = note.steps(self) {
+                        if (note_steps - steps).rem_euclid(divisions) == 0 {
+                            let half_cost = (half_sharps.abs() + 1) / 2;
+                            let cost = ups.abs() + half_cost;
+                            candidates.push(((cost, half_cost), note, note_steps));
+                        }
+                    }
+                }
+            }
+        }
+
+        let (_, note, note_steps) = candidates.into_iter().min_by_key(|&(cost, _, _)| cost)?;
+        Some((note, (steps - note_steps).div_euclid(divisions)))
+    }
+}
+
+// The accidentals `Edo::spell` tries, simplest first.
+const HALF_SHARPS: [i8; 9] = [0, 2, -2, 1, -1, 4, -4, 3, -3];
+const UPS: [i8; 7] = [0, 1, -1, 2, -2, 3, -3];
+
+impl fmt::Display for Edo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-EDO", self.divisions)
+    }
+}
+
+/// A note spelled for an equal division of the octave: a letter with any
+/// number of sharps or flats, half-sharps or half-flats, and ups or downs,
+/// each of which moves the note by one step.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct EdoNote {
+    pub note_name: NoteName,
+    /// Sharps counted in halves, so that 2 is a sharp and -1 a half-flat.
+    pub half_sharps: i8,
+    pub ups: i8,
+}
+
+impl EdoNote {
+    pub const fn new(note_name: NoteName, half_sharps: i8, ups: i8) -> Self {
+        EdoNote {
+            note_name,
+            half_sharps,
+            ups,
+        }
+    }
+
+    /// The steps from C up to this note, not reduced to an octave, so that
+    /// B# is the size of an octave plus a diesis above C in 31-EDO. `None`
+    /// if the note has a half accidental that `edo` can't express.
+    pub fn steps(self, edo: Edo) -> Option<i32> {
+        Some(
+            edo.letter_steps(self.note_name)
+                + edo.half_sharp_steps(self.half_sharps)?
+                + self.ups as i32,
+        )
+    }
+
+    /// The twelve-tone note, if this one has only whole sharps or flats.
+    pub fn note(self) -> Option<Note> {
+        if self.ups != 0 || self.half_sharps % 2 != 0 {
+            return None;
+        }
+        Accidental::from_interval(self.half_sharps / 2)
+            .map(|accidental| Note(self.note_name, accidental))
+    }
+
+    /// Transposes up by `interval`, letter-correctly, keeping any half
+    /// accidentals and ups or downs. `None` if the accidental would
+    /// overflow.
+    pub fn transpose(self, interval: Interval) -> Option<Self> {
+        EdoPitch(self, 4).transpose(interval).map(EdoPitch::note)
+    }
+
+    pub fn transpose_down(self, interval: Interval) -> Option<Self> {
+        EdoPitch(self, 4)
+            .transpose_down(interval)
+            .map(EdoPitch::note)
+    }
+}
+
+impl From<Note> for EdoNote {
+    fn from(note: Note) -> Self {
+        EdoNote::new(note.note_name(), 2 * note.accidental().interval(), 0)
+    }
+}
+
+impl fmt::Display for EdoNote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ups = if self.ups > 0 { "^" } else { "v" };
+        let sharps = if self.half_sharps > 0 { "#" } else { "b" };
+        let half = match self.half_sharps % 2 {
+            1 => "+",
+            -1 => "d",
+            _ => "",
+        };
+        write!(
+            f,
+            "{}{}{}{}",
+            ups.repeat(self.ups.unsigned_abs() as usize),
+            self.note_name,
+            sharps.repeat((self.half_sharps / 2).unsigned_abs() as usize),
+            half
+        )
+    }
+}
+
+/// Parses English note names with ups (`^`) or downs (`v`) before the
+/// letter and any mix of accidentals after it: `#`, `b`, `x`, `♯`, `♭`, `𝄪`
+/// and `𝄫`, `+` or `𝄲` for a half-sharp, and `d` or `𝄳` for a half-flat.
+impl FromStr for EdoNote {
+    type Err = ParseNoteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let err = |kind, position| ParseNoteError {
+            kind,
+            position,
+            input: s.to_string(),
+        };
+
+        if chars.is_empty() {
+            return Err(err(ParseNoteErrorKind::Empty, 0));
+        }
+
+        let len = chars.iter().take_while(|&&c| c == '^' || c == 'v').count();
+        let ups = chars[..len].iter().filter(|&&c| c == '^').count();
+        if ups != 0 && ups != len {
+            return Err(err(ParseNoteErrorKind::MixedAccidentals, 0));
+        }
+        let ups =
+            i8::try_from(len).map_err(|_| err(ParseNoteErrorKind::AccidentalOutOfRange, 0))?;
+        let ups = if chars.first() == Some(&'v') {
+            -ups
+        } else {
+            ups
+        };
+
+        let note_name = match chars.get(len).copied() {
+            Some('A') => NoteName::A,
+            Some('B') => NoteName::B,
+            Some('C') => NoteName::C,
+            Some('D') => NoteName::D,
+            Some('E') => NoteName::E,
+            Some('F') => NoteName::F,
+            Some('G') => NoteName::G,
+            _ => return Err(err(ParseNoteErrorKind::InvalidNoteName, len)),
+        };
+
+        let mut half_sharps = 0i8;
+        for (i, &c) in chars.iter().enumerate().skip(len + 1) {
+            let change = match c {
+                '#' | '♯' => 2,
+                'x' | '𝄪' => 4,
+                'b' | '♭' => -2,
+                '𝄫' => -4,
+                '+' | '𝄲' => 1,
+                'd' | '𝄳' => -1,
+                _ => return Err(err(ParseNoteErrorKind::InvalidAccidental, i)),
+            };
+            half_sharps = half_sharps
+                .checked_add(change)
+                .ok_or_else(|| err(ParseNoteErrorKind::AccidentalOutOfRange, i))?;
+        }
+
+        Ok(EdoNote::new(note_name, half_sharps, ups))
+    }
+}
+
+/// An `EdoNote` in an octave, numbered as for `Pitch`.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct EdoPitch(pub EdoNote, pub i8);
+
+impl EdoPitch {
+    /// The simplest spelling of the pitch `steps` above C-1, if its octave
+    /// fits in an `i8`.
+    pub fn from_steps(edo: Edo, steps: i32) -> Option<Self> {
+        let (note, octaves) = edo.spell(steps);
+        Some(EdoPitch(note, i8::try_from(octaves - 1).ok()?))
+    }
+
+    pub const fn note(self) -> EdoNote {
+        self.0
+    }
+
+    pub const fn octave(self) -> i8 {
+        self.1
+    }
+
+    /// The steps above C-1, if `edo` can express the pitch's accidentals.
+    pub fn steps(self, edo: Edo) -> Option<i32> {
+        Some((self.1 as i32 + 1) * edo.divisions as i32 + self.0.steps(edo)?)
+    }
+
+    pub fn diatonic_steps(self) -> i16 {
+        (self.1 as i16 + 1) * 7 + self.0.note_name.steps_above_c() as i16
+    }
+
+    /// Transposes up by `interval`, letter-correctly, keeping any half
+    /// accidentals and ups or downs. `None` if the accidental or octave
+    /// would overflow.
+    pub fn transpose(self, interval: Interval) -> Option<Self> {
+        self.shift(interval.number() as i16 - 1, interval.semitones())
+    }
+
+    pub fn transpose_down(self, interval: Interval) -> Option<Self> {
+        self.shift(1 - interval.number() as i16, -interval.semitones())
+    }
+
+    // Moves the letter by `steps` and corrects the accidental so that the
+    // note moves by `semitones` in twelve-tone terms.
+    fn shift(self, steps: i16, semitones: i16) -> Option<Self> {
+        let diatonic_steps = self.diatonic_steps() + steps;
+        let note_name = NoteName::from_steps_above_c(diatonic_steps.rem_euclid(7) as u8);
+        let octave = diatonic_steps.div_euclid(7) - 1;
+
+        let natural = |note_name: NoteName, octave: i16| {
+            (octave + 1) * 12 + note_name.semitones_above_c() as i16
+        };
+        let moved = natural(note_name, octave) - natural(self.0.note_name, self.1 as i16);
+        let half_sharps = i8::try_from(semitones - moved)
+            .ok()
+            .and_then(|sharps| sharps.checked_mul(2))
+            .and_then(|half_sharps| self.0.half_sharps.checked_add(half_sharps))?;
+
+        Some(EdoPitch(
+            EdoNote::new(note_name, half_sharps, self.0.ups),
+            i8::try_from(octave).ok()?,
+        ))
+    }
+}
+
+impl From<Pitch> for EdoPitch {
+    fn from(pitch: Pitch) -> Self {
+        EdoPitch(pitch.note().into(), pitch.octave())
+    }
+}
+
+impl fmt::Display for EdoPitch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.0, self.1)
+    }
+}
+
+impl FromStr for EdoPitch {
+    type Err = UnrecognizedPitch;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c.is_ascii_digit() || c == '-')
+            .map(|(i, _)| i)
+            .ok_or_else(|| UnrecognizedPitch(s.to_string()))?;
+
+        let note = s[..split]
+            .parse::<EdoNote>()
+            .map_err(|_| UnrecognizedPitch(s.to_string()))?;
+        let octave = s[split..]
+            .parse::<i8>()
+            .map_err(|_| UnrecognizedPitch(s.to_string()))?;
+
+        Ok(EdoPitch(note, octave))
+    }
+}
+
+/// An equal division of the octave with A4 at `reference` hertz.
+/// Twelve-tone pitches are tuned by their spelling.
+#[derive(fmt::Debug, Copy, Clone, PartialEq)]
+pub struct EdoTuning {
+    pub edo: Edo,
+    pub reference: f64,
+}
+
+impl EdoTuning {
+    pub const fn new(edo: Edo, reference: f64) -> Self {
+        EdoTuning { edo, reference }
+    }
+
+    /// The frequency of `pitch` in hertz, if the division can express its
+    /// accidentals.
+    pub fn edo_frequency(&self, pitch: EdoPitch) -> Option<f64> {
+        let a4 = EdoPitch(EdoNote::new(NoteName::A, 0, 0), 4);
+        let steps = pitch.steps(self.edo)? - a4.steps(self.edo)?;
+        Some(self.reference * tuning::ratio(steps as f64 * self.edo.step_cents()))
+    }
+}
+
+impl From<Edo> for EdoTuning {
+    fn from(edo: Edo) -> Self {
+        EdoTuning::new(edo, CONCERT_A)
+    }
+}
+
+impl Tuning for EdoTuning {
+    fn frequency(&self, pitch: Pitch) -> f64 {
+        self.edo_frequency(pitch.into())
+            .expect("twelve-tone accidentals exist in every division")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::tests::{assert_close, pitch};
+    use crate::tuning::EqualTemperament;
+
+    fn note(s: &str) -> EdoNote {
+        s.parse().unwrap()
+    }
+
+    fn edo_pitch(s: &str) -> EdoPitch {
+        s.parse().unwrap()
+    }
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_divisions() {
+        assert_eq!(Edo::new(12), Some(Edo::TWELVE));
+        assert_eq!(Edo::new(19), Some(Edo::NINETEEN));
+        assert_eq!(Edo::new(24), Some(Edo::TWENTY_FOUR));
+        assert_eq!(Edo::new(31), Some(Edo::THIRTY_ONE));
+        assert_eq!(Edo::new(53), Some(Edo::FIFTY_THREE));
+        assert_eq!(Edo::new(14), None);
+        assert_eq!(Edo::new(0), None);
+        assert_eq!(Edo::new(108), None);
+        assert_eq!(Edo::new(1200), None);
+
+        let sizes = |edo: Edo| (edo.sharp(), edo.diatonic_semitone());
+        assert_eq!(sizes(Edo::TWELVE), (1, 1));
+        assert_eq!(sizes(Edo::NINETEEN), (1, 2));
+        assert_eq!(sizes(Edo::TWENTY_FOUR), (2, 2));
+        assert_eq!(sizes(Edo::THIRTY_ONE), (2, 3));
+        assert_eq!(sizes(Edo::FIFTY_THREE), (5, 4));
+
+        assert_eq!(Edo::NINETEEN.interval_steps(interval("M3")), 6);
+        assert_eq!(Edo::THIRTY_ONE.interval_steps(interval("A4")), 15);
+        assert_eq!(Edo::THIRTY_ONE.interval_steps(interval("d5")), 16);
+        assert_eq!(Edo::FIFTY_THREE.interval_steps(interval("P12")), 84);
+        assert_eq!(Edo::TWELVE.interval_steps(interval("d4")), 4);
+    }
+
+    #[test]
+    fn test_notes() {
+        assert_eq!(note("C"), EdoNote::new(NoteName::C, 0, 0));
+        assert_eq!(note("^Eb"), EdoNote::new(NoteName::E, -2, 1));
+        assert_eq!(note("vvG#+"), EdoNote::new(NoteName::G, 3, -2));
+        assert_eq!(note("Bd"), note("B𝄳"));
+        assert_eq!(note("C𝄪"), note("Cx"));
+        assert_eq!(
+            "^vC".parse::<EdoNote>().unwrap_err().kind,
+            ParseNoteErrorKind::MixedAccidentals
+        );
+        assert_eq!(
+            "Cq".parse::<EdoNote>().unwrap_err().kind,
+            ParseNoteErrorKind::InvalidAccidental
+        );
+        let sharps = format!("C{}", "#".repeat(64));
+        assert_eq!(
+            sharps.parse::<EdoNote>().unwrap_err(),
+            ParseNoteError {
+                kind: ParseNoteErrorKind::AccidentalOutOfRange,
+                position: 64,
+                input: sharps.clone(),
+            }
+        );
+        assert_eq!(note(&format!("C{}", "#".repeat(63))).half_sharps, 126);
+        let downs = format!("{}C", "v".repeat(128));
+        assert_eq!(
+            downs.parse::<EdoNote>().unwrap_err().kind,
+            ParseNoteErrorKind::AccidentalOutOfRange
+        );
+        assert_eq!(note(&format!("{}C", "v".repeat(127))).ups, -127);
+        for s in &["C", "^Eb", "vvG#+", "Bd", "F##", "Abbd"] {
+            assert_eq!(note(s).to_string(), *s);
+        }
+
+        assert_eq!(note("F#").note(), Some("F#".parse().unwrap()));
+        assert_eq!(note("F#+").note(), None);
+        assert_eq!(
+            EdoNote::from(Note(NoteName::B, Accidental::Flat)),
+            note("Bb")
+        );
+
+        let steps = |s: &str, edo: Edo| note(s).steps(edo);
+        assert_eq!(steps("G#", Edo::NINETEEN), Some(12));
+        assert_eq!(steps("Ab", Edo::NINETEEN), Some(13));
+        assert_eq!(steps("C+", Edo::TWENTY_FOUR), Some(1));
+        assert_eq!(steps("Ed", Edo::THIRTY_ONE), Some(9));
+        assert_eq!(steps("B#", Edo::THIRTY_ONE), Some(30));
+        assert_eq!(steps("^D", Edo::FIFTY_THREE), Some(10));
+        assert_eq!(steps("C+", Edo::FIFTY_THREE), None);
+        assert_eq!(steps("C+", Edo::TWELVE), None);
+    }
+
+    #[test]
+    fn test_spell() {
+        let spell = |edo: Edo| -> Vec<String> {
+            (0..edo.divisions() as i32)
+                .map(|steps| edo.spell(steps).0.to_string())
+                .collect()
+        };
+        assert_eq!(
+            spell(Edo::TWELVE),
+            ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"]
+        );
+        assert_eq!(
+            spell(Edo::NINETEEN)[..7],
+            ["C", "C#", "Db", "D", "D#", "Eb", "E"]
+        );
+        assert_eq!(
+            spell(Edo::TWENTY_FOUR)[..6],
+            ["C", "C+", "C#", "Dd", "D", "D+"]
+        );
+        assert_eq!(
+            spell(Edo::THIRTY_ONE)[..6],
+            ["C", "C+", "C#", "Db", "Dd", "D"]
+        );
+        assert_eq!(
+            spell(Edo::FIFTY_THREE)[..10],
+            ["C", "^C", "^^C", "vDb", "Db", "C#", "^C#", "vvD", "vD", "D"]
+        );
+
+        assert_eq!(Edo::THIRTY_ONE.spell(-1), (note("B#"), -1));
+        assert_eq!(Edo::THIRTY_ONE.spell(-2), (note("Cb"), 0));
+        assert_eq!(Edo::THIRTY_ONE.spell(33), (note("C#"), 1));
+        assert_eq!(
+            EdoPitch::from_steps(Edo::TWENTY_FOUR, 121),
+            Some(edo_pitch("C+4"))
+        );
+        assert_eq!(EdoPitch::from_steps(Edo::TWELVE, 59), Some(edo_pitch("B3")));
+        assert_eq!(EdoPitch::from_steps(Edo::TWELVE, 12 * 200), None);
+    }
+
+    #[test]
+    fn test_spell_every_division() {
+        for divisions in 1..=u16::MAX {
+            let edo = match Edo::new(divisions) {
+                Some(edo) => edo,
+                None => continue,
+            };
+            for steps in 0..divisions as i32 {
+                let (note, octave) = edo.spell(steps);
+                assert_eq!(
+                    note.steps(edo).unwrap() + octave * divisions as i32,
+                    steps,
+                    "{} in {}",
+                    note,
+                    edo
+                );
+            }
+        }
+
+        // A sharp in 40-EDO is a single step, but a whole tone is six, so
+        // one step between C and D needs an up or a down.
+        let edo = Edo::new(40).unwrap();
+        assert_eq!(edo.spell(2).0, note("Cx"));
+        assert_eq!(edo.spell(3).0, note("^^^C"));
+    }
+
+    #[test]
+    fn test_transpose() {
+        assert_eq!(note("C+").transpose(interval("M3")), Some(note("E+")));
+        assert_eq!(note("^Eb").transpose(interval("P5")), Some(note("^Bb")));
+        assert_eq!(note("Gd").transpose(interval("m3")), Some(note("Bbd")));
+        assert_eq!(note("Fx").transpose_down(interval("A2")), Some(note("E")));
+        assert_eq!(
+            edo_pitch("Bd3").transpose(interval("m2")),
+            Some(edo_pitch("Cd4"))
+        );
+        assert_eq!(
+            edo_pitch("vA4").transpose_down(interval("P8")),
+            Some(edo_pitch("vA3"))
+        );
+
+        let sharps = note(&format!("C{}", "#".repeat(63)));
+        assert_eq!(sharps.transpose(interval("A1")), None);
+        assert_eq!(sharps.transpose_down(interval("d1")), None);
+        assert_eq!(edo_pitch("C127").transpose(interval("P8")), None);
+        let wide = Interval::from_number_and_semitones(1, 200).unwrap();
+        assert_eq!(edo_pitch("C4").transpose(wide), None);
+
+        // A transposition keeps its size in steps.
+        let edo = Edo::THIRTY_ONE;
+        let start = edo_pitch("E+2");
+        for s in &["m2", "A4", "d7", "M10"] {
+            let interval = interval(s);
+            let end = start.transpose(interval).unwrap();
+            assert_eq!(
+                end.steps(edo).unwrap() - start.steps(edo).unwrap(),
+                edo.interval_steps(interval)
+            );
+        }
+    }
+
+    #[test]
+    fn test_tuning() {
+        let twelve = EdoTuning::from(Edo::TWELVE);
+        let equal = EqualTemperament::default();
+        for s in &["C-1", "A4", "Bb3", "F#6", "B#3"] {
+            assert_close(twelve.frequency(pitch(s)), equal.frequency(pitch(s)));
+        }
+
+        let thirty_one = EdoTuning::from(Edo::THIRTY_ONE);
+        let cents = |tuning: &EdoTuning, low: &str, high: &str| {
+            tuning::cents(tuning.frequency(pitch(high)) / tuning.frequency(pitch(low)))
+        };
+        assert_close(thirty_one.frequency(pitch("A4")), 440.0);
+        assert_close(cents(&thirty_one, "C4", "E4"), 387.1);
+        assert_close(cents(&thirty_one, "G#4", "Ab4"), 38.71);
+
+        let quarter_tones = EdoTuning::from(Edo::TWENTY_FOUR);
+        assert_close(
+            quarter_tones.edo_frequency(edo_pitch("A+4")).unwrap(),
+            440.0 * tuning::ratio(50.0),
+        );
+        assert_eq!(
+            EdoTuning::from(Edo::FIFTY_THREE).edo_frequency(edo_pitch("A+4")),
+            None
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+
+use crate::chord::quality::ChordQuality;
+use crate::chord::symbol::{ChordSymbol, SymbolStyle};
+use crate::degree::{self, Degree};
+use crate::interval::Interval;
+use crate::key::Key;
+use crate::note::{Note, UnspellableNote};
+use crate::scale::modal::ModalScale;
+use crate::scale::Scale;
+
+pub mod chord_scale;
+pub mod roman;
+
+/// How many thirds to stack on each scale degree.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ChordSize {
+    Triad,
+    Seventh,
+    Ninth,
+}
+
+impl ChordSize {
+    pub const fn note_count(self) -> usize {
+        match self {
+            ChordSize::Triad => 3,
+            ChordSize::Seventh => 4,
+            ChordSize::Ninth => 5,
+        }
+    }
+}
+
+/// A chord built by stacking thirds from a scale's own notes.
+#[derive(fmt::Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DiatonicChord {
+    /// The scale degree the chord is built on, counting the tonic as 0.
+    pub step: usize,
+    pub root: Note,
+    pub quality: ChordQuality,
+}
+
+impl DiatonicChord {
+    /// The Roman numeral for the chord, e.g. "ii", "V7" or "viiø7".
+    pub fn numeral(&self) -> String {
+        const NUMERALS: [&str; 12] = [
+            "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII",
+        ];
+
+        let minor = self.quality.degrees().contains(&degree::flat(3));
+        let numeral = NUMERALS[self.step % NUMERALS.len()];
+        let mut result = if minor {
+            numeral.to_lowercase()
+        } else {
+            numeral.to_string()
+        };
+        result.push_str(&numeral_suffix(&self.quality, minor));
+        result
+    }
+
+    pub fn symbol(&self) -> ChordSymbol {
+        ChordSymbol::new(self.root, self.quality.clone())
+    }
+
+    pub fn spell(&self) -> Vec<Note> {
+        self.quality.spell(self.root)
+    }
+}
+
+impl fmt::Display for DiatonicChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.numeral())
+    }
+}
+
+// The case of a numeral already says minor, so "m7" is just "7".
+fn numeral_suffix(quality: &ChordQuality, minor: bool) -> String {
+    let symbol = quality.symbol(SymbolStyle::Plain);
+    let replacements = [("dim7", "°7"), ("m7b5", "ø7"), ("dim", "°"), ("aug", "+")];
+    for &(from, to) in replacements.iter() {
+        if let Some(rest) = symbol.strip_prefix(from) {
+            return format!("{}{}", to, rest);
+        }
+    }
+
+    let degrees = quality.degrees();
+    if degrees.contains(&degree::natural(3)) && degrees.contains(&degree::sharp(5)) {
+        let rest = symbol
+            .replace("(#5)", "")
+            .replace("#5,", "")
+            .replace(",#5", "");
+        return format!("+{}", rest);
+    }
+
+    match symbol.strip_prefix('m') {
+        Some(rest) if minor && !symbol.starts_with("maj") => rest.to_string(),
+        _ => symbol,
+    }
+}
+
+/// The chords on each degree of `scale` from `root`, stacking every other
+/// scale note until the chord would double its root. Chords with a note that
+/// can't be spelled are left out.
+pub fn diatonic_chords(scale: &Scale, root: Note, size: ChordSize) -> Vec<DiatonicChord> {
+    let notes = scale.spell_each(root);
+    (0..notes.len())
+        .filter_map(|step| stack(&notes, step, size).ok())
+        .collect()
+}
+
+/// The chord on one degree of `scale`, as in `diatonic_chords`.
+pub fn diatonic_chord(
+    scale: &Scale,
+    root: Note,
+    step: usize,
+    size: ChordSize,
+) -> Result<DiatonicChord, UnspellableNote> {
+    stack(&scale.spell_each(root), step, size)
+}
+
+fn stack(
+    notes: &[Result<Note, UnspellableNote>],
+    step: usize,
+    size: ChordSize,
+) -> Result<DiatonicChord, UnspellableNote> {
+    let len = notes.len();
+    let root = notes[step % len]?;
+    let degrees = (0..size.note_count())
+        .map(|k| 2 * k)
+        .take_while(|&offset| offset == 0 || offset % len != 0)
+        .map(|offset| {
+            let interval = Interval::between(root, notes[(step + offset) % len]?);
+            let interval = if offset >= len {
+                interval.compound(1)
+            } else {
+                Some(interval)
+            };
+            Ok(interval
+                .and_then(Degree::from_interval)
+                .expect("stacked scale notes must be within a double accidental"))
+        })
+        .collect::<Result<Vec<Degree>, UnspellableNote>>()?;
+
+    Ok(DiatonicChord {
+        step,
+        root,
+        quality: ChordQuality::new(degrees),
+    })
+}
+
+impl ModalScale {
+    pub fn diatonic_chords(&self, root: Note, size: ChordSize) -> Vec<DiatonicChord> {
+        diatonic_chords(&self.to_scale(), root, size)
+    }
+}
+
+impl Key {
+    pub fn diatonic_chords(self, size: ChordSize) -> Vec<DiatonicChord> {
+        self.scale().diatonic_chords(self.tonic(), size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::catalogue;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    fn table(chords: &[DiatonicChord]) -> Vec<String> {
+        chords
+            .iter()
+            .map(|chord| format!("{} {}", chord.numeral(), chord.symbol()))
+            .collect()
+    }
+
+    #[test]
+    fn test_major_key() {
+        let key = Key::major(note("D"));
+        assert_eq!(
+            table(&key.diatonic_chords(ChordSize::Triad)),
+            [
+                "I D",
+                "ii Em",
+                "iii F#m",
+                "IV G",
+                "V A",
+                "vi Bm",
+                "vii° C#dim"
+            ]
+        );
+        assert_eq!(
+            table(&key.diatonic_chords(ChordSize::Seventh)),
+            [
+                "Imaj7 Dmaj7",
+                "ii7 Em7",
+                "iii7 F#m7",
+                "IVmaj7 Gmaj7",
+                "V7 A7",
+                "vi7 Bm7",
+                "viiø7 C#m7b5"
+            ]
+        );
+
+        let ninths = key.diatonic_chords(ChordSize::Ninth);
+        assert_eq!(ninths[4].symbol().to_string(), "A9");
+        assert_eq!(ninths[4].spell(), ["A", "C#", "E", "G", "B"].map(note));
+        assert_eq!(ninths[0].numeral(), "Imaj9");
+    }
+
+    #[test]
+    fn test_other_scales() {
+        let harmonic_minor = catalogue::find("Harmonic Minor").unwrap();
+        let chords = diatonic_chords(&harmonic_minor, note("A"), ChordSize::Seventh);
+        let numerals: Vec<String> = chords.iter().map(|chord| chord.numeral()).collect();
+        assert_eq!(
+            numerals,
+            ["i(maj7)", "iiø7", "III+maj7", "iv7", "V7", "VImaj7", "vii°7"]
+        );
+        assert_eq!(chords[6].spell(), ["G#", "B", "D", "F"].map(note));
+
+        let dorian = ModalScale::new(catalogue::find("Major").unwrap(), 1.into()).unwrap();
+        let chords = dorian.diatonic_chords(note("D"), ChordSize::Triad);
+        assert_eq!(chords[3].to_string(), "IV");
+        assert_eq!(chords[3].symbol().to_string(), "G");
+
+        let pentatonic = catalogue::find("Major Pentatonic").unwrap();
+        let chords = diatonic_chords(&pentatonic, note("C"), ChordSize::Triad);
+        assert_eq!(chords[0].spell(), ["C", "E", "A"].map(note));
+        assert_eq!(chords[4].spell(), ["A", "D", "G"].map(note));
+
+        // Stacking whole-tone thirds returns to the root after three notes.
+        let whole_tone = catalogue::find("Whole Tone").unwrap();
+        let chords = diatonic_chords(&whole_tone, note("C"), ChordSize::Seventh);
+        assert_eq!(chords[0].spell(), ["C", "E", "G#"].map(note));
+        assert_eq!(chords[0].numeral(), "I+");
+    }
+
+    #[test]
+    fn test_unspellable_chords_are_left_out() {
+        // From Fb the altered scale's fourth note would be Bbbb, so only the
+        // chords without it are left.
+        let altered = catalogue::find("Altered").unwrap();
+        let chords = diatonic_chords(&altered, note("Fb"), ChordSize::Triad);
+        let steps: Vec<usize> = chords.iter().map(|chord| chord.step).collect();
+        assert_eq!(steps, [0, 2, 4, 5]);
+        assert_eq!(chords[1].spell(), ["Abb", "Cbb", "Ebb"].map(note));
+        assert!(diatonic_chord(&altered, note("Fb"), 3, ChordSize::Triad).is_err());
+    }
+
+    #[test]
+    fn test_every_scale_from_every_root() {
+        use crate::accidental::Accidental::*;
+        use crate::note_name::NoteName::*;
+
+        let note_names = [C, D, E, F, G, A, B];
+        let accidentals = [DoubleFlat, Flat, Natural, Sharp, DoubleSharp];
+        let sizes = [ChordSize::Triad, ChordSize::Seventh, ChordSize::Ninth];
+
+        for family in catalogue::FAMILIES {
+            for rotation in 0..family.len() {
+                let scale = family.mode(rotation).unwrap();
+                for &note_name in note_names.iter() {
+                    for &accidental in accidentals.iter() {
+                        let root = Note(note_name, accidental);
+                        let notes = scale.spell_each(root);
+                        for &size in sizes.iter() {
+                            for chord in diatonic_chords(&scale, root, size) {
+                                assert_eq!(Ok(chord.root), notes[chord.step]);
+                                assert_eq!(chord.spell().len(), chord.quality.degrees().len());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
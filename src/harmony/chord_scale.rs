@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+
+use crate::accidental::Accidental;
+use crate::chord::quality::ChordQuality;
+use crate::chord::registry::{ChordDefinition, DEFINITIONS};
+use crate::chord::symbol::ChordSymbol;
+use crate::degree::{self, Degree};
+use crate::interval::Interval;
+use crate::note::Note;
+use crate::scale::catalogue::{ScaleFamily, FAMILIES};
+use crate::scale::Scale;
+
+/// What a scale note does over a chord.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ToneRole {
+    ChordTone,
+    /// A note that can be added to the chord.
+    Tension,
+    /// A half step above a chord tone, except a dominant chord's flat ninth.
+    Avoid,
+}
+
+/// A catalogue mode on a chord's root that contains every chord tone.
+#[derive(Debug, Clone)]
+pub struct ChordScale {
+    pub family: &'static ScaleFamily,
+    pub rotation: usize,
+    /// Each scale note as a degree above the chord root, writing 2, 4 and 6
+    /// as 9, 11 and 13 outside the chord.
+    pub tones: Vec<(Degree, ToneRole)>,
+}
+
+impl ChordScale {
+    pub fn name(&self) -> String {
+        self.family.mode_name(self.rotation)
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.family.mode(self.rotation).unwrap()
+    }
+
+    pub fn tensions(&self) -> Vec<Degree> {
+        self.with_role(ToneRole::Tension)
+    }
+
+    pub fn avoid_notes(&self) -> Vec<Degree> {
+        self.with_role(ToneRole::Avoid)
+    }
+
+    fn with_role(&self, role: ToneRole) -> Vec<Degree> {
+        self.tones
+            .iter()
+            .filter(|&&(_, r)| r == role)
+            .map(|&(degree, _)| degree)
+            .collect()
+    }
+
+    // Fewer avoid notes first, then fewer altered tensions.
+    fn rank(&self) -> (usize, usize) {
+        let altered = self
+            .tensions()
+            .iter()
+            .filter(|degree| degree.accidental() != Accidental::Natural)
+            .count();
+        (self.avoid_notes().len(), altered)
+    }
+}
+
+/// The catalogue modes that contain every tone of the chord, with the fewest
+/// avoid notes and then altered tensions first. Modes repeating an earlier
+/// one's notes, or that can't be spelled from `root`, are left out.
+pub fn scales_for_chord(root: Note, quality: &ChordQuality) -> Vec<ChordScale> {
+    let chord: Vec<(Degree, u8)> = quality
+        .degrees()
+        .iter()
+        .map(|&degree| (degree, pitch_class(degree.interval())))
+        .collect();
+    let dominant =
+        quality.degree(3) == Some(degree::natural(3)) && quality.degree(7) == Some(degree::flat(7));
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for family in FAMILIES {
+        for rotation in 0..family.len() {
+            let scale = family.mode(rotation).unwrap();
+            let pitch_classes = pitch_classes(&scale);
+            if seen.contains(&pitch_classes)
+                || !chord.iter().all(|(_, pc)| pitch_classes.contains(pc))
+            {
+                continue;
+            }
+
+            let notes = match scale.try_spell(root) {
+                Ok(notes) => notes,
+                Err(_) => continue,
+            };
+            let tones = notes
+                .into_iter()
+                .map(|note| {
+                    let interval = Interval::between(root, note);
+                    let pc = pitch_class(interval);
+                    if let Some(&(degree, _)) = chord.iter().find(|&&(_, c)| c == pc) {
+                        return Some((degree, ToneRole::ChordTone));
+                    }
+
+                    let interval = match interval.number() {
+                        2 | 4 | 6 => interval.compound(1)?,
+                        _ => interval,
+                    };
+                    let degree = Degree::from_interval(interval)?;
+                    let flat_ninth = dominant && degree == self::degree::flat(9);
+                    let clashes = chord.iter().any(|&(_, c)| (c + 1) % 12 == pc);
+                    if clashes && !flat_ninth {
+                        Some((degree, ToneRole::Avoid))
+                    } else {
+                        Some((degree, ToneRole::Tension))
+                    }
+                })
+                .collect::<Option<_>>();
+            let tones = match tones {
+                Some(tones) => tones,
+                None => continue,
+            };
+
+            seen.insert(pitch_classes);
+            results.push(ChordScale {
+                family,
+                rotation,
+                tones,
+            });
+        }
+    }
+
+    // The sort is stable, so ties stay in catalogue order.
+    results.sort_by_key(ChordScale::rank);
+    results
+}
+
+/// A chord from the registry whose tones all lie in a scale.
+#[derive(Debug, Clone)]
+pub struct SupportedChord {
+    /// The scale degree the chord is built on, counting the tonic as 0.
+    pub step: usize,
+    pub root: Note,
+    pub definition: &'static ChordDefinition,
+}
+
+impl SupportedChord {
+    pub fn symbol(&self) -> ChordSymbol {
+        ChordSymbol::new(self.root, self.definition.quality())
+    }
+}
+
+/// Every registry chord built on a note of `scale` from only its notes.
+pub fn chords_for_scale(scale: &Scale, root: Note) -> Vec<SupportedChord> {
+    let pitch_classes = pitch_classes(scale);
+    let offsets: Vec<u8> = pitch_classes_in_order(scale);
+
+    let mut results = Vec::new();
+    for (step, note) in scale.spell(root).into_iter().enumerate() {
+        for definition in DEFINITIONS {
+            let fits = definition.quality().degrees().iter().all(|degree| {
+                let pc = (offsets[step] + pitch_class(degree.interval())) % 12;
+                pitch_classes.contains(&pc)
+            });
+            if fits {
+                results.push(SupportedChord {
+                    step,
+                    root: note,
+                    definition,
+                });
+            }
+        }
+    }
+    results
+}
+
+fn pitch_class(interval: Interval) -> u8 {
+    interval.semitones().rem_euclid(12) as u8
+}
+
+// The scale's notes in semitones above its root.
+fn pitch_classes_in_order(scale: &Scale) -> Vec<u8> {
+    if scale.intervals.is_empty() {
+        return Vec::new();
+    }
+
+    let mut offset = 0;
+    let mut offsets = vec![0];
+    for interval in &scale.intervals[..scale.intervals.len() - 1] {
+        offset += interval.size();
+        offsets.push(offset % 12);
+    }
+    offsets
+}
+
+fn pitch_classes(scale: &Scale) -> Vec<u8> {
+    let mut offsets = pitch_classes_in_order(scale);
+    offsets.sort_unstable();
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chord::quality::{sevenths, triads};
+    use crate::scale::catalogue;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    fn degree(s: &str) -> Degree {
+        s.parse().unwrap()
+    }
+
+    fn degrees(s: &str) -> Vec<Degree> {
+        crate::degree::parse_degrees(s).unwrap()
+    }
+
+    fn find<'a>(scales: &'a [ChordScale], name: &str) -> Option<&'a ChordScale> {
+        scales.iter().find(|scale| scale.name() == name)
+    }
+
+    #[test]
+    fn test_scales_for_chord() {
+        let cmaj7 = scales_for_chord(note("C"), &sevenths::MAJOR);
+        assert_eq!(cmaj7[0].name(), "Lydian");
+        assert_eq!(cmaj7[0].tensions(), degrees("9 #11 13"));
+        assert!(cmaj7[0].avoid_notes().is_empty());
+        let ionian = find(&cmaj7, "Major").unwrap();
+        assert_eq!(ionian.avoid_notes(), degrees("11"));
+        assert_eq!(ionian.tensions(), degrees("9 13"));
+        assert!(find(&cmaj7, "Major Pentatonic").is_none());
+
+        let g7 = scales_for_chord(note("G"), &sevenths::DOMINANT);
+        assert_eq!(g7[0].name(), "Lydian Dominant");
+        assert_eq!(
+            find(&g7, "Mixolydian").unwrap().avoid_notes(),
+            degrees("11")
+        );
+        let diminished = find(&g7, "Half-Whole Diminished").unwrap();
+        assert_eq!(diminished.tensions(), degrees("b9 #9 #11 13"));
+        assert!(diminished.avoid_notes().is_empty());
+        assert!(find(&g7, "Altered").is_none());
+
+        let dm7 = scales_for_chord(note("D"), &sevenths::MINOR);
+        assert_eq!(dm7[0].name(), "Dorian");
+        assert_eq!(
+            dm7[0].tones,
+            [
+                (degree("1"), ToneRole::ChordTone),
+                (degree("9"), ToneRole::Tension),
+                (degree("b3"), ToneRole::ChordTone),
+                (degree("11"), ToneRole::Tension),
+                (degree("5"), ToneRole::ChordTone),
+                (degree("13"), ToneRole::Tension),
+                (degree("b7"), ToneRole::ChordTone),
+            ]
+        );
+        assert_eq!(
+            find(&dm7, "Phrygian").unwrap().avoid_notes(),
+            degrees("b9 b13")
+        );
+
+        // The rotations of the whole tone scale are listed once.
+        let augmented = scales_for_chord(note("C"), &triads::AUGMENTED);
+        let whole_tone = augmented
+            .iter()
+            .filter(|scale| scale.name() == "Whole Tone")
+            .count();
+        assert_eq!(whole_tone, 1);
+
+        // From Cbb, modes that would need a triple accidental are left out.
+        let cbb = scales_for_chord(note("Cbb"), &triads::MAJOR);
+        assert!(find(&cbb, "Major").is_some());
+        assert!(find(&cbb, "Lydian").is_some());
+        assert!(find(&cbb, "Mixolydian").is_none());
+        for scale in &cbb {
+            assert!(
+                scale.scale().try_spell(note("Cbb")).is_ok(),
+                "{}",
+                scale.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_chords_for_scale() {
+        let major = catalogue::find("Major").unwrap();
+        let chords = chords_for_scale(&major, note("C"));
+        let symbols: Vec<String> = chords
+            .iter()
+            .filter(|chord| chord.definition.quality().note_count() == 4)
+            .filter(|chord| chord.definition.name.ends_with("seventh"))
+            .map(|chord| chord.symbol().to_string())
+            .collect();
+        assert_eq!(
+            symbols,
+            ["Cmaj7", "Dm7", "Em7", "Fmaj7", "G7", "Am7", "Bm7b5"]
+        );
+
+        assert!(chords
+            .iter()
+            .any(|chord| chord.step == 4 && chord.definition.name == "dominant thirteenth"));
+        assert!(!chords
+            .iter()
+            .any(|chord| chord.symbol().to_string() == "D7"));
+
+        let blues = catalogue::find("Blues").unwrap();
+        let chords = chords_for_scale(&blues, note("A"));
+        assert!(chords
+            .iter()
+            .any(|chord| chord.symbol().to_string() == "Am7"));
+        assert!(chords
+            .iter()
+            .all(|chord| chord.definition.name != "major" || chord.root != note("A")));
+
+        let empty = Scale::from_intervals("Empty", vec![]);
+        assert!(chords_for_scale(&empty, note("C")).is_empty());
+    }
+}
@@ -0,0 +1,855 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::accidental::Accidental;
+use crate::chord::quality::ChordQuality;
+use crate::chord::symbol::ChordSymbol;
+use crate::degree::{self, Degree};
+use crate::harmony::{self, ChordSize};
+use crate::interval::canonical::CanonicalInterval;
+use crate::interval::Interval;
+use crate::key::Key;
+use crate::note::{Note, UnspellableNote};
+use crate::scale::mode::Mode;
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Alteration {
+    /// Written ° or o: a diminished fifth, and a diminished seventh if there
+    /// is one.
+    Diminished,
+    /// Written ø: a diminished fifth and a minor seventh.
+    HalfDiminished,
+    /// Written +: an augmented fifth.
+    Augmented,
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Seventh {
+    /// The seventh the key's scale gives, so that V7 is a dominant seventh and
+    /// IV7 a major seventh in a major key. Chromatic chords such as bVII7
+    /// take a minor seventh.
+    Diatonic,
+    /// A major seventh whatever the key, written "maj7" or "M7".
+    Major,
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AugmentedSixth {
+    Italian,
+    French,
+    German,
+}
+
+impl AugmentedSixth {
+    fn degrees(self) -> Vec<Degree> {
+        use degree::{natural, sharp};
+
+        match self {
+            AugmentedSixth::Italian => vec![natural(1), natural(3), sharp(6)],
+            AugmentedSixth::French => vec![natural(1), natural(3), sharp(4), sharp(6)],
+            AugmentedSixth::German => vec![natural(1), natural(3), natural(5), sharp(6)],
+        }
+    }
+
+    fn abbreviation(self) -> &'static str {
+        match self {
+            AugmentedSixth::Italian => "It",
+            AugmentedSixth::French => "Fr",
+            AugmentedSixth::German => "Ger",
+        }
+    }
+}
+
+#[derive(fmt::Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Numeral {
+    /// A chord on a degree of the key's scale, raised or lowered by
+    /// `accidental`. `step` counts the tonic as 0, and `major` is the case of
+    /// the numeral.
+    ScaleDegree {
+        accidental: Accidental,
+        step: u8,
+        major: bool,
+        alteration: Option<Alteration>,
+        seventh: Option<Seventh>,
+    },
+    /// A major triad on the lowered second degree.
+    Neapolitan,
+    /// An augmented sixth chord, with the lowered sixth degree in the bass.
+    AugmentedSixth(AugmentedSixth),
+    /// The tonic triad in second inversion, standing in for the dominant.
+    Cadential,
+}
+
+/// A chord named by its place in a key rather than by its root, e.g. "ii7",
+/// "V65", "bVI", "N6" or "viio7/ii".
+#[derive(fmt::Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RomanNumeral {
+    pub numeral: Numeral,
+    /// Which chord tone is in the bass, counting the root as 0.
+    pub inversion: usize,
+    /// The chord this one is applied to, e.g. the second V of "V7/V".
+    pub target: Option<Box<RomanNumeral>>,
+}
+
+const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+impl RomanNumeral {
+    /// Spells the chord in `key`. An applied chord is spelled in the major or
+    /// minor key of its target. In a minor key, a diminished or
+    /// half-diminished chord on the seventh degree is built on the raised
+    /// leading tone, as in vii°7.
+    pub fn realise(&self, key: Key) -> Result<ChordSymbol, UnspellableNote> {
+        let key = match &self.target {
+            None => key,
+            Some(target) => {
+                let chord = target.realise(key)?;
+                if chord.quality.degrees().contains(&degree::flat(3)) {
+                    Key::minor(chord.root)
+                } else {
+                    Key::major(chord.root)
+                }
+            }
+        };
+
+        let notes = key.spell();
+        let (root, quality) = match self.numeral {
+            Numeral::ScaleDegree {
+                accidental,
+                step,
+                major,
+                alteration,
+                seventh,
+            } => {
+                let step = step as usize % notes.len();
+                let raised = key.mode() == Mode::AEOLIAN
+                    && step == 6
+                    && matches!(
+                        alteration,
+                        Some(Alteration::Diminished) | Some(Alteration::HalfDiminished)
+                    );
+                let root = alter(notes[step], accidental.interval() + raised as i8)?;
+
+                let mut degrees = vec![
+                    degree::natural(1),
+                    if major {
+                        degree::natural(3)
+                    } else {
+                        degree::flat(3)
+                    },
+                    match alteration {
+                        None => degree::natural(5),
+                        Some(Alteration::Augmented) => degree::sharp(5),
+                        Some(_) => degree::flat(5),
+                    },
+                ];
+                let seventh = match (alteration, seventh) {
+                    (Some(Alteration::HalfDiminished), _) => Some(degree::flat(7)),
+                    (_, None) => None,
+                    (Some(Alteration::Diminished), Some(_)) => Some(degree::double_flat(7)),
+                    (_, Some(Seventh::Major)) => Some(degree::natural(7)),
+                    // A chromatic root has no seventh in the scale, so it
+                    // takes a minor one, as in bVII7.
+                    (_, Some(Seventh::Diatonic)) if accidental != Accidental::Natural => {
+                        Some(degree::flat(7))
+                    }
+                    (_, Some(Seventh::Diatonic)) => {
+                        let interval = Interval::between(root, notes[(step + 6) % notes.len()]);
+                        Some(if interval.semitones() == 11 {
+                            degree::natural(7)
+                        } else {
+                            degree::flat(7)
+                        })
+                    }
+                };
+                degrees.extend(seventh);
+
+                (root, ChordQuality::new(degrees))
+            }
+            Numeral::Neapolitan => (
+                key.tonic()
+                    .transpose(CanonicalInterval::MinorSecond.into())?,
+                ChordQuality::new(vec![
+                    degree::natural(1),
+                    degree::natural(3),
+                    degree::natural(5),
+                ]),
+            ),
+            Numeral::AugmentedSixth(kind) => (
+                key.tonic()
+                    .transpose(CanonicalInterval::MinorSixth.into())?,
+                ChordQuality::new(kind.degrees()),
+            ),
+            Numeral::Cadential => {
+                let scale = key.scale().to_scale();
+                let tonic = harmony::diatonic_chord(&scale, key.tonic(), 0, ChordSize::Triad)?;
+                (tonic.root, tonic.quality)
+            }
+        };
+
+        let tones = quality.try_spell(root)?;
+        let symbol = ChordSymbol::new(root, quality);
+        Ok(match tones.get(self.inversion) {
+            Some(&bass) if self.inversion > 0 => symbol.with_bass(bass),
+            _ => symbol,
+        })
+    }
+}
+
+fn alter(note: Note, semitones: i8) -> Result<Note, UnspellableNote> {
+    Accidental::from_interval(note.accidental().interval() + semitones)
+        .map(|accidental| Note(note.note_name(), accidental))
+        .ok_or(UnspellableNote {
+            note_name: note.note_name(),
+            pitch: (note.pitch() as i8 + semitones).rem_euclid(12) as u8,
+        })
+}
+
+/// Names `chord` with a Roman numeral in `key`, if it's one a numeral can
+/// spell. Chords made of the key's own notes are named on their scale degree;
+/// otherwise the Neapolitan and augmented sixths are tried, then secondary
+/// dominants and leading-tone chords, and finally chromatic chords such as
+/// bVI.
+pub fn label(key: Key, chord: &ChordSymbol) -> Option<RomanNumeral> {
+    let notes = key.spell();
+    let diatonic = chord.spell().iter().all(|note| notes.contains(note));
+
+    let mut candidates = Vec::new();
+    if diatonic {
+        candidates.extend(readings(key, chord));
+    }
+
+    for inversion in 0..2 {
+        candidates.push(RomanNumeral {
+            numeral: Numeral::Neapolitan,
+            inversion,
+            target: None,
+        });
+    }
+    for &kind in [
+        AugmentedSixth::Italian,
+        AugmentedSixth::French,
+        AugmentedSixth::German,
+    ]
+    .iter()
+    {
+        candidates.push(RomanNumeral {
+            numeral: Numeral::AugmentedSixth(kind),
+            inversion: 0,
+            target: None,
+        });
+    }
+
+    for target in key.diatonic_chords(ChordSize::Triad) {
+        if target.step == 0 {
+            continue;
+        }
+        let degrees = target.quality.degrees();
+        if degrees.contains(&degree::flat(5)) {
+            continue;
+        }
+        let major = !degrees.contains(&degree::flat(3));
+        let target_key = if major {
+            Key::major(target.root)
+        } else {
+            Key::minor(target.root)
+        };
+        let target = RomanNumeral {
+            numeral: Numeral::ScaleDegree {
+                accidental: Accidental::Natural,
+                step: target.step as u8,
+                major,
+                alteration: None,
+                seventh: None,
+            },
+            inversion: 0,
+            target: None,
+        };
+
+        for reading in readings(target_key, chord) {
+            let applied = matches!(
+                reading.numeral,
+                Numeral::ScaleDegree {
+                    accidental: Accidental::Natural,
+                    step: 4,
+                    major: true,
+                    alteration: None,
+                    ..
+                } | Numeral::ScaleDegree {
+                    accidental: Accidental::Natural,
+                    step: 6,
+                    alteration: Some(Alteration::Diminished) | Some(Alteration::HalfDiminished),
+                    ..
+                }
+            );
+            if applied {
+                candidates.push(RomanNumeral {
+                    target: Some(Box::new(target.clone())),
+                    ..reading
+                });
+            }
+        }
+    }
+
+    candidates.extend(readings(key, chord));
+
+    candidates
+        .into_iter()
+        .find(|candidate| candidate.realise(key).is_ok_and(|c| same_chord(&c, chord)))
+}
+
+// The numerals on the chord's root that might spell it in `key`.
+fn readings(key: Key, chord: &ChordSymbol) -> Vec<RomanNumeral> {
+    let notes = key.spell();
+    let root = chord.root;
+    let step = (root.note_name().steps_above_c() + 7 - key.tonic().note_name().steps_above_c()) % 7;
+    let base = notes[step as usize];
+    let mut shift = (root.pitch() as i8 - base.pitch() as i8).rem_euclid(12);
+    if shift > 6 {
+        shift -= 12;
+    }
+
+    let degrees = chord.quality.degrees();
+    let has = |degree| degrees.contains(&degree);
+    let major = !has(degree::flat(3));
+    let alteration = if has(degree::flat(3)) && has(degree::flat(5)) && has(degree::flat(7)) {
+        Some(Alteration::HalfDiminished)
+    } else if has(degree::flat(3)) && has(degree::flat(5)) {
+        Some(Alteration::Diminished)
+    } else if has(degree::natural(3)) && has(degree::sharp(5)) {
+        Some(Alteration::Augmented)
+    } else {
+        None
+    };
+    let sevenths = if degrees.iter().any(|d| d.number() == 7) {
+        vec![Some(Seventh::Diatonic), Some(Seventh::Major)]
+    } else {
+        vec![None]
+    };
+
+    let inversion = match chord.bass {
+        None => 0,
+        Some(bass) => match chord.quality.spell(root).iter().position(|&n| n == bass) {
+            Some(inversion) => inversion,
+            None => return Vec::new(),
+        },
+    };
+
+    // In a minor key, the raised leading tone is already implied.
+    let mut candidates = Vec::new();
+    for &shift in [shift, shift - 1].iter() {
+        if let Some(accidental) = Accidental::from_interval(shift) {
+            for &seventh in sevenths.iter() {
+                candidates.push(RomanNumeral {
+                    numeral: Numeral::ScaleDegree {
+                        accidental,
+                        step,
+                        major,
+                        alteration,
+                        seventh,
+                    },
+                    inversion,
+                    target: None,
+                });
+            }
+        }
+    }
+    candidates
+}
+
+fn same_chord(a: &ChordSymbol, b: &ChordSymbol) -> bool {
+    let sorted = |chord: &ChordSymbol| {
+        let mut degrees = chord.quality.degrees().to_vec();
+        degrees.sort_by_key(|d| (d.number(), d.semitones()));
+        degrees
+    };
+    let bass = |chord: &ChordSymbol| chord.bass.filter(|&bass| bass != chord.root);
+
+    a.root == b.root && bass(a) == bass(b) && sorted(a) == sorted(b)
+}
+
+impl fmt::Display for RomanNumeral {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const TRIAD_FIGURES: [&str; 3] = ["", "6", "64"];
+        const SEVENTH_FIGURES: [&str; 4] = ["7", "65", "43", "42"];
+
+        match &self.numeral {
+            Numeral::ScaleDegree {
+                accidental,
+                step,
+                major,
+                alteration,
+                seventh,
+            } => {
+                let numeral = NUMERALS[*step as usize % NUMERALS.len()];
+                write!(f, "{}", accidental)?;
+                if *major {
+                    write!(f, "{}", numeral)?;
+                } else {
+                    write!(f, "{}", numeral.to_lowercase())?;
+                }
+                match alteration {
+                    Some(Alteration::Diminished) => write!(f, "°")?,
+                    Some(Alteration::HalfDiminished) => write!(f, "ø")?,
+                    Some(Alteration::Augmented) => write!(f, "+")?,
+                    None => {}
+                }
+
+                if *seventh == Some(Seventh::Major) {
+                    write!(f, "maj")?;
+                }
+                if seventh.is_some() || *alteration == Some(Alteration::HalfDiminished) {
+                    write!(f, "{}", SEVENTH_FIGURES[self.inversion.min(3)])?;
+                } else {
+                    write!(f, "{}", TRIAD_FIGURES[self.inversion.min(2)])?;
+                }
+            }
+            Numeral::Neapolitan => write!(f, "N{}", TRIAD_FIGURES[self.inversion.min(2)])?,
+            Numeral::AugmentedSixth(kind) => write!(f, "{}+6", kind.abbreviation())?,
+            Numeral::Cadential => write!(f, "Cad64")?,
+        }
+
+        if let Some(target) = &self.target {
+            write!(f, "/{}", target)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseNumeralErrorKind {
+    Empty,
+    UnrecognizedNumeral,
+    UnrecognizedFigure,
+}
+
+/// Why a Roman numeral couldn't be parsed, and the character position at
+/// which the problem starts.
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct ParseNumeralError {
+    pub kind: ParseNumeralErrorKind,
+    pub position: usize,
+    pub input: String,
+}
+
+impl fmt::Display for ParseNumeralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ParseNumeralErrorKind::*;
+
+        let description = match self.kind {
+            Empty => return write!(f, "empty Roman numeral"),
+            UnrecognizedNumeral => "unrecognized Roman numeral",
+            UnrecognizedFigure => "unrecognized figure",
+        };
+        write!(
+            f,
+            "{} at position {} in {:?}",
+            description, self.position, self.input
+        )
+    }
+}
+
+// Figures in either notation, longer ones before their prefixes, with the
+// inversion each stands for and whether it implies a seventh.
+const FIGURES: [(&str, usize, bool); 13] = [
+    ("6/5", 1, true),
+    ("6/4", 2, false),
+    ("4/3", 2, true),
+    ("4/2", 3, true),
+    ("5/3", 0, false),
+    ("65", 1, true),
+    ("64", 2, false),
+    ("43", 2, true),
+    ("42", 3, true),
+    ("53", 0, false),
+    ("7", 0, true),
+    ("6", 1, false),
+    ("2", 3, true),
+];
+
+fn starts_with(chars: &[char], prefix: &str) -> bool {
+    let prefix: Vec<char> = prefix.chars().collect();
+    chars.starts_with(&prefix)
+}
+
+// Parses one numeral from `chars`, which start at character `offset` of the
+// input.
+fn parse_numeral(
+    chars: &[char],
+    offset: usize,
+    err: &impl Fn(ParseNumeralErrorKind, usize) -> ParseNumeralError,
+) -> Result<RomanNumeral, ParseNumeralError> {
+    let mut position = 0;
+    let eat = |position: &mut usize, prefix: &str| {
+        let found = starts_with(&chars[*position..], prefix);
+        if found {
+            *position += prefix.chars().count();
+        }
+        found
+    };
+
+    let special = if eat(&mut position, "Cad") {
+        Some(Numeral::Cadential)
+    } else if eat(&mut position, "N") {
+        Some(Numeral::Neapolitan)
+    } else if eat(&mut position, "It") {
+        Some(Numeral::AugmentedSixth(AugmentedSixth::Italian))
+    } else if eat(&mut position, "Fr") {
+        Some(Numeral::AugmentedSixth(AugmentedSixth::French))
+    } else if eat(&mut position, "Ger") {
+        Some(Numeral::AugmentedSixth(AugmentedSixth::German))
+    } else {
+        None
+    };
+
+    let numeral = match special {
+        Some(numeral) => {
+            if let Numeral::AugmentedSixth(_) = numeral {
+                eat(&mut position, "+");
+            }
+            numeral
+        }
+        None => {
+            let mut shift = 0i8;
+            while let Some(&c) = chars.get(position) {
+                match c {
+                    'b' | '♭' => shift -= 1,
+                    '#' | '♯' => shift += 1,
+                    _ => break,
+                }
+                position += 1;
+            }
+            let accidental = Accidental::from_interval(shift)
+                .ok_or_else(|| err(ParseNumeralErrorKind::UnrecognizedNumeral, offset))?;
+
+            let start = position;
+            while chars
+                .get(position)
+                .is_some_and(|&c| matches!(c, 'I' | 'V' | 'i' | 'v'))
+            {
+                position += 1;
+            }
+            let letters: String = chars[start..position].iter().collect();
+            let major = letters.chars().all(|c| c.is_uppercase());
+            let step = NUMERALS
+                .iter()
+                .position(|&numeral| numeral == letters.to_uppercase())
+                .filter(|_| major || letters.chars().all(|c| c.is_lowercase()))
+                .ok_or_else(|| err(ParseNumeralErrorKind::UnrecognizedNumeral, offset + start))?;
+
+            let alteration = match chars.get(position) {
+                Some('°') | Some('o') => Some(Alteration::Diminished),
+                Some('ø') | Some('Ø') => Some(Alteration::HalfDiminished),
+                Some('+') => Some(Alteration::Augmented),
+                _ => None,
+            };
+            if alteration.is_some() {
+                position += 1;
+            }
+
+            let major_seventh = eat(&mut position, "maj") || eat(&mut position, "M");
+
+            Numeral::ScaleDegree {
+                accidental,
+                step: step as u8,
+                major,
+                alteration,
+                seventh: if major_seventh {
+                    Some(Seventh::Major)
+                } else {
+                    None
+                },
+            }
+        }
+    };
+
+    let figure_start = position;
+    let figure = FIGURES
+        .iter()
+        .find(|&&(figure, _, _)| starts_with(&chars[position..], figure));
+    if let Some(&(figure, _, _)) = figure {
+        position += figure.chars().count();
+    }
+
+    let (numeral, inversion) = match numeral {
+        Numeral::ScaleDegree {
+            accidental,
+            step,
+            major,
+            alteration,
+            seventh,
+        } => {
+            let (inversion, has_seventh) = figure.map_or((0, false), |&(_, i, s)| (i, s));
+            let seventh = match seventh {
+                Some(_) if !has_seventh => {
+                    return Err(err(
+                        ParseNumeralErrorKind::UnrecognizedFigure,
+                        offset + figure_start,
+                    ))
+                }
+                Some(seventh) => Some(seventh),
+                None if has_seventh || alteration == Some(Alteration::HalfDiminished) => {
+                    Some(Seventh::Diatonic)
+                }
+                None => None,
+            };
+            let numeral = Numeral::ScaleDegree {
+                accidental,
+                step,
+                major,
+                alteration,
+                seventh,
+            };
+            (numeral, inversion)
+        }
+        Numeral::Neapolitan => match figure {
+            None => (Numeral::Neapolitan, 0),
+            Some(&(_, inversion, false)) => (Numeral::Neapolitan, inversion),
+            Some(_) => {
+                return Err(err(
+                    ParseNumeralErrorKind::UnrecognizedFigure,
+                    offset + figure_start,
+                ))
+            }
+        },
+        // The figures of an augmented sixth name the chord rather than its
+        // inversion: It6, Fr43, Ger65.
+        Numeral::AugmentedSixth(kind) => (Numeral::AugmentedSixth(kind), 0),
+        Numeral::Cadential => match figure {
+            None | Some(&(_, 2, false)) => (Numeral::Cadential, 2),
+            Some(_) => {
+                return Err(err(
+                    ParseNumeralErrorKind::UnrecognizedFigure,
+                    offset + figure_start,
+                ))
+            }
+        },
+    };
+
+    let target = match chars.get(position) {
+        None => None,
+        Some('/') => Some(Box::new(parse_numeral(
+            &chars[position + 1..],
+            offset + position + 1,
+            err,
+        )?)),
+        Some(_) => {
+            return Err(err(
+                ParseNumeralErrorKind::UnrecognizedFigure,
+                offset + position,
+            ))
+        }
+    };
+
+    Ok(RomanNumeral {
+        numeral,
+        inversion,
+        target,
+    })
+}
+
+impl FromStr for RomanNumeral {
+    type Err = ParseNumeralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let err = |kind, position| ParseNumeralError {
+            kind,
+            position,
+            input: s.to_string(),
+        };
+
+        if chars.is_empty() {
+            return Err(err(ParseNumeralErrorKind::Empty, 0));
+        }
+        parse_numeral(&chars, 0, &err)
+    }
+}
+
+/// Parses a progression of numerals separated by whitespace, e.g. "ii7 V7/V
+/// V7 I".
+pub fn parse_progression(s: &str) -> Result<Vec<RomanNumeral>, ParseNumeralError> {
+    let chars: Vec<char> = s.chars().collect();
+    let err = |kind, position| ParseNumeralError {
+        kind,
+        position,
+        input: s.to_string(),
+    };
+
+    let mut numerals = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        if chars[position].is_whitespace() {
+            position += 1;
+            continue;
+        }
+        let start = position;
+        while chars.get(position).is_some_and(|c| !c.is_whitespace()) {
+            position += 1;
+        }
+        numerals.push(parse_numeral(&chars[start..position], start, &err)?);
+    }
+
+    if numerals.is_empty() {
+        return Err(err(ParseNumeralErrorKind::Empty, 0));
+    }
+    Ok(numerals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    fn key(tonic: &str, mode: Mode) -> Key {
+        Key::new(note(tonic), mode).unwrap()
+    }
+
+    fn realise(numerals: &str, key: Key) -> Vec<String> {
+        parse_progression(numerals)
+            .unwrap()
+            .iter()
+            .map(|numeral| numeral.realise(key).unwrap().to_string())
+            .collect()
+    }
+
+    fn spell(numeral: &str, key: Key) -> Vec<Note> {
+        numeral
+            .parse::<RomanNumeral>()
+            .unwrap()
+            .realise(key)
+            .unwrap()
+            .spell()
+    }
+
+    #[test]
+    fn test_realise() {
+        let c_major = key("C", Mode::IONIAN);
+        let c_minor = key("C", Mode::AEOLIAN);
+
+        assert_eq!(realise("ii7 V7/V V7 I", c_major), ["Dm7", "D7", "G7", "C"]);
+        assert_eq!(
+            realise("I IV7 vii°7 viiø7 V65 V43 V42 I6 Cad64", c_major),
+            ["C", "Fmaj7", "Bdim7", "Bm7b5", "G7/B", "G7/D", "G7/F", "C/E", "C/G"]
+        );
+        assert_eq!(
+            realise("i iv6 VI V7 vii°7 VII III+ Cad64", c_minor),
+            ["Cm", "Fm/Ab", "Ab", "G7", "Bdim7", "Bb", "Ebaug", "Cm/G"]
+        );
+        assert_eq!(
+            realise("bVI bVII7 IVmaj7 IM7", c_major),
+            ["Ab", "Bb7", "Fmaj7", "Cmaj7"]
+        );
+        assert_eq!(realise("viio7/ii V/V/V", c_major), ["C#dim7", "A"]);
+
+        assert_eq!(spell("N6", c_minor), ["F", "Db", "Ab"].map(note));
+        assert_eq!(spell("It+6", c_minor), ["Ab", "C", "F#"].map(note));
+        assert_eq!(spell("Fr43", c_major), ["Ab", "C", "D", "F#"].map(note));
+        assert_eq!(
+            spell("Ger+6", key("A", Mode::AEOLIAN)),
+            ["F", "A", "C", "D#"].map(note)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for &s in [
+            "I",
+            "ii7",
+            "V65",
+            "V43/V",
+            "viiø7",
+            "vii°7/ii",
+            "bVI",
+            "#iv°",
+            "III+",
+            "IVmaj7",
+            "N6",
+            "It+6",
+            "Fr+6",
+            "Ger+6",
+            "Cad64",
+            "V/V/V",
+            "i64",
+        ]
+        .iter()
+        {
+            assert_eq!(s.parse::<RomanNumeral>().unwrap().to_string(), s);
+        }
+
+        let equivalent = [
+            ("viio7", "vii°7"),
+            ("V6/5", "V65"),
+            ("IM7", "Imaj7"),
+            ("viiø", "viiø7"),
+            ("Ger65", "Ger+6"),
+            ("Cad6/4", "Cad64"),
+        ];
+        for &(s, expected) in equivalent.iter() {
+            assert_eq!(s.parse::<RomanNumeral>().unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseNumeralErrorKind::*;
+
+        let error = |s: &str| s.parse::<RomanNumeral>().unwrap_err();
+
+        assert_eq!(error("").kind, Empty);
+        assert_eq!(error("iV").kind, UnrecognizedNumeral);
+        assert_eq!(error("VIII").kind, UnrecognizedNumeral);
+        let e = error("IVx");
+        assert_eq!((e.kind, e.position), (UnrecognizedFigure, 2));
+        let e = error("V7/");
+        assert_eq!((e.kind, e.position), (UnrecognizedNumeral, 3));
+        assert_eq!(error("Imaj").kind, UnrecognizedFigure);
+        assert_eq!(error("N7").kind, UnrecognizedFigure);
+
+        let e = parse_progression("I  V? vi").unwrap_err();
+        assert_eq!((e.kind, e.position), (UnrecognizedFigure, 4));
+        assert_eq!(
+            e.to_string(),
+            "unrecognized figure at position 4 in \"I  V? vi\""
+        );
+        assert_eq!(parse_progression(" ").unwrap_err().kind, Empty);
+    }
+
+    #[test]
+    fn test_label() {
+        let c_major = key("C", Mode::IONIAN);
+        let c_minor = key("C", Mode::AEOLIAN);
+        let label = |key: Key, symbol: &str| {
+            let chord: ChordSymbol = symbol.parse().unwrap();
+            super::label(key, &chord).map(|numeral| numeral.to_string())
+        };
+
+        assert_eq!(label(c_major, "Dm7").unwrap(), "ii7");
+        assert_eq!(label(c_major, "G7/B").unwrap(), "V65");
+        assert_eq!(label(c_major, "Fmaj7").unwrap(), "IV7");
+        assert_eq!(label(c_major, "C/G").unwrap(), "I64");
+        assert_eq!(label(c_major, "D7").unwrap(), "V7/V");
+        assert_eq!(label(c_major, "C#dim7").unwrap(), "vii°7/ii");
+        assert_eq!(label(c_major, "Ab").unwrap(), "bVI");
+        assert_eq!(label(c_major, "Cmaj7/E").unwrap(), "I65");
+        assert_eq!(label(c_minor, "Db/F").unwrap(), "N6");
+        assert_eq!(label(c_minor, "Bdim7").unwrap(), "vii°7");
+        assert_eq!(label(c_minor, "G7").unwrap(), "V7");
+        assert_eq!(label(c_minor, "Eb").unwrap(), "III");
+        assert_eq!(label(c_major, "Csus4"), None);
+
+        let german = ChordSymbol::new(note("Ab"), ChordQuality::from_degrees("1 3 5 #6").unwrap());
+        assert_eq!(super::label(c_major, &german).unwrap().to_string(), "Ger+6");
+
+        for &numeral in ["ii7", "V7/V", "viiø7", "bVI", "N6", "It+6", "V42"].iter() {
+            let chord = numeral
+                .parse::<RomanNumeral>()
+                .unwrap()
+                .realise(c_major)
+                .unwrap();
+            assert_eq!(super::label(c_major, &chord).unwrap().to_string(), numeral);
+        }
+    }
+}
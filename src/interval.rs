@@ -1 +1,370 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::interval::canonical::CanonicalInterval;
+use crate::interval::quality::IntervalQuality;
+use crate::note::Note;
+use crate::pitch::Pitch;
+
 pub mod canonical;
+pub mod quality;
+
+const MAJOR_SCALE_SEMITONES: [i16; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// An interval with a quality and a generic number, e.g. a minor third (m3) or
+/// an augmented eleventh (A11).
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Interval {
+    quality: IntervalQuality,
+    number: u8,
+}
+
+impl Interval {
+    pub fn new(quality: IntervalQuality, number: u8) -> Option<Self> {
+        use IntervalQuality::*;
+
+        let valid = match quality {
+            _ if number == 0 => false,
+            Diminished(0) | Augmented(0) => false,
+            Diminished(_) | Augmented(_) => true,
+            Perfect => Self::is_perfect_number(number),
+            Minor | Major => !Self::is_perfect_number(number),
+        };
+
+        if valid {
+            Some(Interval { quality, number })
+        } else {
+            None
+        }
+    }
+
+    /// The interval with the given generic number spanning `semitones`, if the
+    /// two can be reconciled with some quality.
+    pub fn from_number_and_semitones(number: u8, semitones: i16) -> Option<Self> {
+        use IntervalQuality::*;
+
+        if number == 0 {
+            return None;
+        }
+
+        let offset = semitones - Self::major_or_perfect_semitones(number);
+        let quality = if Self::is_perfect_number(number) {
+            match offset {
+                0 => Perfect,
+                n if n > 0 => Augmented(n as u8),
+                n => Diminished(-n as u8),
+            }
+        } else {
+            match offset {
+                0 => Major,
+                -1 => Minor,
+                n if n > 0 => Augmented(n as u8),
+                n => Diminished((-n - 1) as u8),
+            }
+        };
+
+        Self::new(quality, number)
+    }
+
+    /// The ascending interval from `lower` up to the next `upper` within an
+    /// octave, so that C to D# is an augmented second and C to Cb is a
+    /// diminished octave.
+    pub fn between(lower: Note, upper: Note) -> Self {
+        let steps =
+            upper.note_name().steps_above_c() as i16 - lower.note_name().steps_above_c() as i16;
+        let steps = steps.rem_euclid(7);
+        let base = MAJOR_SCALE_SEMITONES[steps as usize];
+
+        let mut semitones = (upper.pitch() as i16 - lower.pitch() as i16).rem_euclid(12);
+        if semitones - base > 6 {
+            semitones -= 12;
+        } else if semitones - base < -6 {
+            semitones += 12;
+        }
+
+        let interval = if steps == 0 && semitones < 0 {
+            Self::from_number_and_semitones(8, semitones + 12)
+        } else {
+            u8::try_from(steps + 1)
+                .ok()
+                .and_then(|number| Self::from_number_and_semitones(number, semitones))
+        };
+        interval.expect("an interval within an octave always has a quality")
+    }
+
+    /// The interval spanned by two pitches, in either order, or `None` if it's
+    /// too wide for its number to fit in a `u8`.
+    pub fn between_pitches(a: Pitch, b: Pitch) -> Option<Self> {
+        let mut steps = b.diatonic_steps() - a.diatonic_steps();
+        let mut semitones = b.semitones() - a.semitones();
+        if steps < 0 || (steps == 0 && semitones < 0) {
+            steps = -steps;
+            semitones = -semitones;
+        }
+
+        let number = u8::try_from(steps + 1).ok()?;
+        Self::from_number_and_semitones(number, semitones)
+    }
+
+    pub const fn quality(self) -> IntervalQuality {
+        self.quality
+    }
+
+    pub const fn number(self) -> u8 {
+        self.number
+    }
+
+    /// The number reduced to a single octave, in `1..=7`.
+    pub const fn simple_number(self) -> u8 {
+        (self.number - 1) % 7 + 1
+    }
+
+    /// How many whole octaves the interval spans beyond its simple form.
+    pub const fn octaves(self) -> u8 {
+        (self.number - 1) / 7
+    }
+
+    pub fn semitones(self) -> i16 {
+        use IntervalQuality::*;
+
+        let offset = match self.quality {
+            Diminished(n) if Self::is_perfect_number(self.number) => -(n as i16),
+            Diminished(n) => -(n as i16) - 1,
+            Minor => -1,
+            Perfect | Major => 0,
+            Augmented(n) => n as i16,
+        };
+
+        Self::major_or_perfect_semitones(self.number) + offset
+    }
+
+    pub const fn is_compound(self) -> bool {
+        self.number > 8
+    }
+
+    /// Reduces a compound interval to within an octave. Octaves and their
+    /// compounds reduce to an octave rather than a unison.
+    pub fn simple(self) -> Self {
+        let number = match self.simple_number() {
+            _ if !self.is_compound() => self.number,
+            1 => 8,
+            n => n,
+        };
+        Interval { number, ..self }
+    }
+
+    /// The interval `octaves` octaves wider, or `None` if its number would
+    /// overflow.
+    pub fn compound(self, octaves: u8) -> Option<Self> {
+        let number = octaves
+            .checked_mul(7)
+            .and_then(|steps| self.number.checked_add(steps))?;
+        Some(Interval { number, ..self })
+    }
+
+    /// Inverts the interval's simple form, so that M3 becomes m6, A4 becomes d5
+    /// and P1 becomes P8.
+    pub fn invert(self) -> Self {
+        Interval {
+            quality: self.quality.inverse(),
+            number: 9 - self.simple().number,
+        }
+    }
+
+    pub fn canonical(self) -> Option<CanonicalInterval> {
+        match self.semitones() {
+            size @ 0..=17 | size @ 19..=21 => Some(CanonicalInterval::from(size as u8)),
+            _ => None,
+        }
+    }
+
+    const fn is_perfect_number(number: u8) -> bool {
+        matches!((number - 1) % 7, 0 | 3 | 4)
+    }
+
+    fn major_or_perfect_semitones(number: u8) -> i16 {
+        let steps = number as i16 - 1;
+        MAJOR_SCALE_SEMITONES[(steps % 7) as usize] + 12 * (steps / 7)
+    }
+}
+
+impl From<CanonicalInterval> for Interval {
+    fn from(interval: CanonicalInterval) -> Self {
+        const NUMBERS: [u8; 12] = [1, 2, 2, 3, 3, 4, 4, 5, 6, 6, 7, 7];
+
+        let size = interval.size();
+        let number = NUMBERS[(size % 12) as usize] + 7 * (size / 12);
+        Self::from_number_and_semitones(number, size as i16).unwrap()
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:#} {}", self.quality, self.number)
+        } else {
+            write!(f, "{}{}", self.quality, self.number)
+        }
+    }
+}
+
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct UnrecognizedInterval(String);
+
+impl FromStr for Interval {
+    type Err = UnrecognizedInterval;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use IntervalQuality::*;
+
+        let err = || UnrecognizedInterval(s.to_string());
+
+        let split = s.find(|c: char| c.is_ascii_digit()).ok_or_else(err)?;
+        let (quality, number) = s.split_at(split);
+
+        let quality = match quality {
+            "m" => Minor,
+            "P" => Perfect,
+            "M" => Major,
+            q if !q.is_empty() && q.chars().all(|c| c == 'd') => Diminished(q.len() as u8),
+            q if !q.is_empty() && q.chars().all(|c| c == 'A') => Augmented(q.len() as u8),
+            _ => return Err(err()),
+        };
+        let number = number.parse::<u8>().map_err(|_| err())?;
+
+        Interval::new(quality, number).ok_or_else(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accidental::Accidental;
+    use crate::note_name::NoteName;
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        use IntervalQuality::*;
+
+        assert_eq!(interval("m3"), Interval::new(Minor, 3).unwrap());
+        assert_eq!(interval("A4"), Interval::new(Augmented(1), 4).unwrap());
+        assert_eq!(interval("d7"), Interval::new(Diminished(1), 7).unwrap());
+        assert_eq!(interval("P12"), Interval::new(Perfect, 12).unwrap());
+        assert_eq!(interval("AA2"), Interval::new(Augmented(2), 2).unwrap());
+        assert_eq!(interval("dd5").to_string(), "dd5");
+        assert_eq!(format!("{:#}", interval("dd5")), "Doubly Diminished 5");
+
+        for s in &["", "3", "m", "P3", "M5", "m0", "x3", "Ad4", "m3m"] {
+            assert!(s.parse::<Interval>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_semitones() {
+        let expected = [
+            ("P1", 0),
+            ("A1", 1),
+            ("d2", 0),
+            ("m2", 1),
+            ("A2", 3),
+            ("m3", 3),
+            ("A4", 6),
+            ("d5", 6),
+            ("d7", 9),
+            ("dd7", 8),
+            ("P8", 12),
+            ("M9", 14),
+            ("A11", 18),
+            ("P12", 19),
+            ("M13", 21),
+            ("P15", 24),
+        ];
+        for &(s, semitones) in expected.iter() {
+            assert_eq!(interval(s).semitones(), semitones, "{}", s);
+            assert_eq!(
+                Interval::from_number_and_semitones(interval(s).number(), semitones),
+                Some(interval(s))
+            );
+        }
+    }
+
+    #[test]
+    fn test_invert_compound_simple() {
+        let inversions = [
+            ("P1", "P8"),
+            ("m2", "M7"),
+            ("A4", "d5"),
+            ("dd3", "AA6"),
+            ("M9", "m7"),
+        ];
+        for &(a, b) in inversions.iter() {
+            assert_eq!(interval(a).invert(), interval(b), "{}", a);
+        }
+
+        assert_eq!(interval("m3").compound(1), Some(interval("m10")));
+        assert_eq!(interval("P5").compound(2), Some(interval("P19")));
+        assert_eq!(interval("P5").compound(36), None);
+        assert_eq!(interval("M251").compound(1), None);
+        assert_eq!(interval("M10").simple(), interval("M3"));
+        assert_eq!(interval("P15").simple(), interval("P8"));
+        assert_eq!(interval("P8").simple(), interval("P8"));
+        assert!(interval("M9").is_compound());
+        assert!(!interval("P8").is_compound());
+    }
+
+    #[test]
+    fn test_canonical() {
+        use crate::interval::canonical::CanonicalInterval::*;
+
+        assert_eq!(Interval::from(MinorThird), interval("m3"));
+        assert_eq!(Interval::from(Tritone), interval("A4"));
+        assert_eq!(Interval::from(Octave), interval("P8"));
+        assert_eq!(Interval::from(PerfectTwelfth), interval("P12"));
+        assert_eq!(Interval::from(MajorThirteenth), interval("M13"));
+
+        assert_eq!(interval("A2").canonical(), Some(MinorThird));
+        assert_eq!(interval("d5").canonical(), Some(Tritone));
+        assert_eq!(interval("A11").canonical(), None);
+    }
+
+    #[test]
+    fn test_between() {
+        let between = |a: &str, b: &str| {
+            Interval::between(a.parse().unwrap(), b.parse().unwrap()).to_string()
+        };
+        assert_eq!(between("C", "D#"), "A2");
+        assert_eq!(between("C", "Eb"), "m3");
+        assert_eq!(between("C", "C"), "P1");
+        assert_eq!(between("C", "C#"), "A1");
+        assert_eq!(between("C", "Cb"), "d8");
+        assert_eq!(between("C", "B#"), "A7");
+        assert_eq!(between("B", "C"), "m2");
+        assert_eq!(between("F#", "C"), "d5");
+        assert_eq!(between("F", "B"), "A4");
+        assert_eq!(between("G", "F"), "m7");
+        assert_eq!(between("Cbb", "C##"), "AAAA1");
+        assert_eq!(between("E#", "Fb"), "dd2");
+
+        let between_pitches = |a: &str, b: &str| {
+            Interval::between_pitches(a.parse().unwrap(), b.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(between_pitches("C4", "E5"), "M10");
+        assert_eq!(between_pitches("E5", "C4"), "M10");
+        assert_eq!(between_pitches("C4", "C4"), "P1");
+        assert_eq!(between_pitches("Cb4", "C4"), "A1");
+        assert_eq!(between_pitches("B#3", "C4"), "d2");
+        assert_eq!(between_pitches("G2", "D5"), "P19");
+        assert_eq!(between_pitches("C4", "Cb5"), "d8");
+
+        let lowest = Pitch(Note(NoteName::C, Accidental::Natural), -128);
+        let highest = Pitch(Note(NoteName::B, Accidental::Natural), 127);
+        assert_eq!(Interval::between_pitches(lowest, highest), None);
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum IntervalQuality {
+    Diminished(u8),
+    Minor,
+    Perfect,
+    Major,
+    Augmented(u8),
+}
+
+impl IntervalQuality {
+    pub const fn inverse(self) -> Self {
+        use IntervalQuality::*;
+        match self {
+            Diminished(n) => Augmented(n),
+            Minor => Major,
+            Perfect => Perfect,
+            Major => Minor,
+            Augmented(n) => Diminished(n),
+        }
+    }
+}
+
+impl fmt::Display for IntervalQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use IntervalQuality::*;
+
+        if f.alternate() {
+            match *self {
+                Diminished(1) => write!(f, "Diminished"),
+                Diminished(2) => write!(f, "Doubly Diminished"),
+                Diminished(n) => write!(f, "{}x Diminished", n),
+                Minor => write!(f, "Minor"),
+                Perfect => write!(f, "Perfect"),
+                Major => write!(f, "Major"),
+                Augmented(1) => write!(f, "Augmented"),
+                Augmented(2) => write!(f, "Doubly Augmented"),
+                Augmented(n) => write!(f, "{}x Augmented", n),
+            }
+        } else {
+            match *self {
+                Diminished(n) => write!(f, "{}", "d".repeat(n as usize)),
+                Minor => write!(f, "m"),
+                Perfect => write!(f, "P"),
+                Major => write!(f, "M"),
+                Augmented(n) => write!(f, "{}", "A".repeat(n as usize)),
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::accidental::Accidental;
+use crate::note::Note;
+use crate::note_name::NoteName;
+use crate::scale::catalogue;
+use crate::scale::modal::ModalScale;
+use crate::scale::mode::Mode;
+
+const SHARP_ORDER: [NoteName; 7] = {
+    use crate::note_name::NoteName::*;
+    [F, C, G, D, A, E, B]
+};
+
+/// A key signature as a position on the circle of fifths: the number of sharps
+/// if positive, or of flats if negative. Theoretical keys beyond seven sharps
+/// or flats use double accidentals.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct KeySignature(pub i8);
+
+impl KeySignature {
+    pub const fn sharps(self) -> u8 {
+        if self.0 > 0 {
+            self.0 as u8
+        } else {
+            0
+        }
+    }
+
+    pub const fn flats(self) -> u8 {
+        if self.0 < 0 {
+            -self.0 as u8
+        } else {
+            0
+        }
+    }
+
+    /// The altered notes in the order they're written in the signature, so F#
+    /// C# G# for three sharps or Bb Eb for two flats.
+    pub fn notes(self) -> Vec<Note> {
+        let count = self.0.unsigned_abs().min(7) as usize;
+        let doubled = self.0.unsigned_abs().saturating_sub(7) as usize;
+
+        (0..count)
+            .map(|i| {
+                let steps = if i < doubled { 2 } else { 1 };
+                if self.0 > 0 {
+                    Note(SHARP_ORDER[i], Accidental::from_interval(steps).unwrap())
+                } else {
+                    Note(
+                        SHARP_ORDER[6 - i],
+                        Accidental::from_interval(-steps).unwrap(),
+                    )
+                }
+            })
+            .collect()
+    }
+
+    pub fn accidental(self, note_name: NoteName) -> Accidental {
+        self.notes()
+            .into_iter()
+            .find(|note| note.note_name() == note_name)
+            .map_or(Accidental::Natural, Note::accidental)
+    }
+}
+
+/// A tonic and a mode of the major scale, e.g. F# minor (Aeolian) or D Dorian.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Key {
+    tonic: Note,
+    mode: Mode,
+}
+
+impl Key {
+    /// The key on `tonic` in `mode`, or `None` if `mode` isn't one of the
+    /// seven modes of the major scale.
+    pub const fn new(tonic: Note, mode: Mode) -> Option<Self> {
+        if mode.rotation() < 7 {
+            Some(Key { tonic, mode })
+        } else {
+            None
+        }
+    }
+
+    pub const fn major(tonic: Note) -> Self {
+        Key {
+            tonic,
+            mode: Mode::IONIAN,
+        }
+    }
+
+    pub const fn minor(tonic: Note) -> Self {
+        Key {
+            tonic,
+            mode: Mode::AEOLIAN,
+        }
+    }
+
+    /// The key in `mode` with the given signature, if `mode` is a mode of the
+    /// major scale and the tonic can be spelled with at most a double
+    /// accidental.
+    pub fn from_signature(signature: KeySignature, mode: Mode) -> Option<Self> {
+        let fifths = signature.0 as i16 + mode_fifths(mode)?;
+        note_from_fifths(fifths).map(|tonic| Key { tonic, mode })
+    }
+
+    pub const fn tonic(self) -> Note {
+        self.tonic
+    }
+
+    pub const fn mode(self) -> Mode {
+        self.mode
+    }
+
+    pub fn signature(self) -> KeySignature {
+        let mode_fifths =
+            mode_fifths(self.mode).expect("a key's mode must be a mode of the major scale");
+        KeySignature((note_fifths(self.tonic) - mode_fifths) as i8)
+    }
+
+    pub fn scale(self) -> ModalScale {
+        ModalScale::new(catalogue::find("Major").unwrap(), self.mode)
+            .expect("a key's mode must be a mode of the major scale")
+    }
+
+    /// The notes of the key from its tonic, one for each letter, with the
+    /// accidental the signature gives that letter. Notes of theoretical keys
+    /// that would need more than a double accidental are spelled
+    /// enharmonically.
+    pub fn spell(self) -> Vec<Note> {
+        let signature = self.signature().0 as i16;
+        let mut note_name = self.tonic.note_name();
+        (0..7)
+            .map(|_| {
+                let natural = Note(note_name, Accidental::Natural);
+                // The signature's notes sit between one fifth below its major
+                // tonic and five above.
+                let sharps = (signature - note_fifths(natural) + 5).div_euclid(7) as i8;
+                note_name = note_name.step_up();
+                Accidental::from_interval(sharps).map_or(natural + sharps, |accidental| {
+                    Note(natural.note_name(), accidental)
+                })
+            })
+            .collect()
+    }
+
+    /// The relative minor of a major key, or the relative major of any other
+    /// mode.
+    pub fn relative(self) -> Option<Self> {
+        let mode = match self.mode {
+            Mode::IONIAN => Mode::AEOLIAN,
+            _ => Mode::IONIAN,
+        };
+        Key::from_signature(self.signature(), mode)
+    }
+
+    /// The parallel minor of a major key, or the parallel major of any other
+    /// mode.
+    pub fn parallel(self) -> Self {
+        let mode = match self.mode {
+            Mode::IONIAN => Mode::AEOLIAN,
+            _ => Mode::IONIAN,
+        };
+        Key { mode, ..self }
+    }
+
+    /// The neighbouring key a fifth above, with one more sharp or one fewer
+    /// flat.
+    pub fn dominant(self) -> Option<Self> {
+        Key::from_signature(KeySignature(self.signature().0 + 1), self.mode)
+    }
+
+    /// The neighbouring key a fifth below, with one more flat or one fewer
+    /// sharp.
+    pub fn subdominant(self) -> Option<Self> {
+        Key::from_signature(KeySignature(self.signature().0 - 1), self.mode)
+    }
+}
+
+// Position on the line of fifths, with C at 0.
+pub(crate) fn note_fifths(note: Note) -> i16 {
+    let index = SHARP_ORDER
+        .iter()
+        .position(|&note_name| note_name == note.note_name())
+        .unwrap() as i16;
+    index - 1 + 7 * note.accidental().interval() as i16
+}
+
+fn note_from_fifths(fifths: i16) -> Option<Note> {
+    let note_name = SHARP_ORDER[(fifths + 1).rem_euclid(7) as usize];
+    let accidental = Accidental::from_interval((fifths + 1).div_euclid(7) as i8)?;
+    Some(Note(note_name, accidental))
+}
+
+// How far the tonic of each mode sits from the tonic of its relative major, or
+// `None` if `mode` isn't a mode of the major scale.
+fn mode_fifths(mode: Mode) -> Option<i16> {
+    const MODE_FIFTHS: [i16; 7] = [0, 2, 4, -1, 1, 3, 5];
+    MODE_FIFTHS.get(mode.rotation()).copied()
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            Mode::IONIAN => write!(f, "{} major", self.tonic),
+            Mode::AEOLIAN => write!(f, "{} minor", self.tonic),
+            _ => write!(f, "{} {}", self.tonic, self.scale().name()),
+        }
+    }
+}
+
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct UnrecognizedKey(String);
+
+impl FromStr for Key {
+    type Err = UnrecognizedKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || UnrecognizedKey(s.to_string());
+
+        let s = s.trim();
+        let split = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| !matches!(c, '#' | 'b' | 'x' | '♯' | '♭' | '𝄪' | '𝄫'))
+            .map_or(s.len(), |(i, _)| i);
+        let (tonic, mode) = s.split_at(split);
+
+        let tonic = tonic.parse::<Note>().map_err(|_| err())?;
+        let mode = match mode.trim() {
+            "" | "M" => Mode::IONIAN,
+            "m" => Mode::AEOLIAN,
+            mode => match mode.to_lowercase().as_str() {
+                "maj" => Mode::IONIAN,
+                "min" => Mode::AEOLIAN,
+                name => match catalogue::find_mode(name) {
+                    Some((family, rotation)) if family.name == "Major" => Mode::new(rotation as u8),
+                    _ => return Err(err()),
+                },
+            },
+        };
+
+        Key::new(tonic, mode).ok_or_else(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> Key {
+        s.parse().unwrap()
+    }
+
+    fn names(notes: Vec<Note>) -> String {
+        notes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_parse() {
+        use crate::accidental::Accidental::*;
+        use crate::note_name::NoteName::*;
+
+        assert_eq!(key("F# minor"), Key::minor(Note(F, Sharp)));
+        assert_eq!(key("Bbm"), Key::minor(Note(B, Flat)));
+        assert_eq!(key("Eb"), Key::major(Note(E, Flat)));
+        assert_eq!(key("C major"), Key::major(Note(C, Natural)));
+        assert_eq!(key("bm"), Key::minor(Note(B, Natural)));
+        assert_eq!(
+            key("D dorian"),
+            Key::new(Note(D, Natural), Mode::DORIAN).unwrap()
+        );
+        assert_eq!(key("F#M"), Key::major(Note(F, Sharp)));
+
+        assert_eq!(key("F# minor").to_string(), "F# minor");
+        assert_eq!(key("Ab").to_string(), "Ab major");
+        assert_eq!(key("G mixolydian").to_string(), "G Mixolydian");
+
+        for s in &["", "H", "C minr", "C#b"] {
+            assert!(s.parse::<Key>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_signature() {
+        assert_eq!(key("C").signature(), KeySignature(0));
+        assert_eq!(key("Am").signature(), KeySignature(0));
+        assert_eq!(key("E").signature(), KeySignature(4));
+        assert_eq!(key("C#").signature(), KeySignature(7));
+        assert_eq!(key("Cb").signature(), KeySignature(-7));
+        assert_eq!(key("Bbm").signature(), KeySignature(-5));
+        assert_eq!(key("D dorian").signature(), KeySignature(0));
+        assert_eq!(key("F lydian").signature(), KeySignature(0));
+        assert_eq!(key("G#").signature(), KeySignature(8));
+
+        assert_eq!(names(key("A").signature().notes()), "F# C# G#");
+        assert_eq!(names(key("Eb").signature().notes()), "Bb Eb Ab");
+        assert_eq!(
+            names(key("G#").signature().notes()),
+            "F## C# G# D# A# E# B#"
+        );
+        assert_eq!(key("Fbm").signature().sharps(), 0);
+        assert_eq!(key("Fbm").signature().flats(), 11);
+    }
+
+    #[test]
+    fn test_spelling_matches_signature() {
+        let modes = [
+            Mode::IONIAN,
+            Mode::DORIAN,
+            Mode::PHRYGIAN,
+            Mode::LYDIAN,
+            Mode::MIXOLYDIAN,
+            Mode::AEOLIAN,
+            Mode::LOCRIAN,
+        ];
+
+        for fifths in -7..=7 {
+            for &mode in modes.iter() {
+                let key = Key::from_signature(KeySignature(fifths), mode).unwrap();
+                assert_eq!(key.signature(), KeySignature(fifths));
+
+                let notes = key.spell();
+                assert_eq!(notes[0], key.tonic());
+                for note in notes {
+                    assert_eq!(
+                        note.accidental(),
+                        key.signature().accidental(note.note_name()),
+                        "{} in {}",
+                        note,
+                        key
+                    );
+                }
+            }
+        }
+
+        assert_eq!(names(key("C# major").spell()), "C# D# E# F# G# A# B#");
+        assert_eq!(names(key("Ebm").spell()), "Eb F Gb Ab Bb Cb Db");
+        assert_eq!(names(key("G# dorian").spell()), "G# A# B C# D# E# F#");
+        assert_eq!(names(key("D# major").spell()), "D# E# F## G# A# B# C##");
+        assert_eq!(names(key("Fb minor").spell()), "Fb Gb Abb Bbb Cb Dbb Ebb");
+    }
+
+    #[test]
+    fn test_related_keys() {
+        assert_eq!(key("C").relative(), Some(key("Am")));
+        assert_eq!(key("F#m").relative(), Some(key("A")));
+        assert_eq!(key("E phrygian").relative(), Some(key("C")));
+        assert_eq!(key("C").parallel(), key("Cm"));
+        assert_eq!(key("Cm").parallel(), key("C"));
+
+        assert_eq!(key("C").dominant(), Some(key("G")));
+        assert_eq!(key("C").subdominant(), Some(key("F")));
+        assert_eq!(key("Bbm").dominant(), Some(key("Fm")));
+        assert_eq!(key("F#").dominant(), Some(key("C#")));
+        assert_eq!(key("Gb").subdominant(), Some(key("Cb")));
+        assert_eq!(key("B##").dominant(), None);
+    }
+
+    #[test]
+    fn test_modes_outside_the_major_scale() {
+        use crate::accidental::Accidental::*;
+        use crate::note_name::NoteName::*;
+
+        assert_eq!(Key::new(Note(C, Natural), Mode::new(7)), None);
+        assert_eq!(Key::from_signature(KeySignature(0), Mode::new(7)), None);
+        assert_eq!(
+            Key::new(Note(C, Natural), Mode::new(6)).map(Key::signature),
+            Some(KeySignature(-5))
+        );
+    }
+}
@@ -1,6 +1,14 @@
 pub mod accidental;
+pub mod audio;
 pub mod chord;
+pub mod degree;
+pub mod edo;
+pub mod harmony;
 pub mod interval;
+pub mod key;
 pub mod note;
 pub mod note_name;
+pub mod pitch;
+pub mod pitch_class;
 pub mod scale;
+pub mod tuning;
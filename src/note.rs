@@ -3,9 +3,16 @@ use std::{fmt, ops};
 
 use crate::accidental::Accidental;
 use crate::interval::canonical::CanonicalInterval;
+use crate::interval::Interval;
+use crate::note::parse::{Notation, ParseNoteError};
 use crate::note_name::NoteName;
 
-#[derive(fmt::Debug, Copy, Clone)]
+pub mod parse;
+
+/// A spelled note. Equality, hashing and ordering go by spelling, so D# and Eb
+/// are different notes; see `is_enharmonic_to` and `PitchClass` for comparing
+/// by sound.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Note(pub NoteName, pub Accidental);
 
 mod prelude {
@@ -34,6 +41,16 @@ impl Note {
         }
     }
 
+    /// Spells `pitch` using the given letter, if that needs no more than a
+    /// double accidental.
+    pub fn spell(note_name: NoteName, pitch: u8) -> Result<Self, UnspellableNote> {
+        let offset = (pitch as i8 - note_name.pitch() as i8 + 18).rem_euclid(12) - 6;
+        match Accidental::from_interval(offset) {
+            Some(accidental) => Ok(Note(note_name, accidental)),
+            None => Err(UnspellableNote { note_name, pitch }),
+        }
+    }
+
     pub const fn note_name(self) -> NoteName {
         self.0
     }
@@ -46,6 +63,10 @@ impl Note {
         (12 + self.note_name().pitch() as i8 + self.accidental().interval()) as u8 % 12
     }
 
+    pub const fn is_enharmonic_to(self, other: Self) -> bool {
+        self.pitch() == other.pitch()
+    }
+
     pub fn enharmonic(self) -> Self {
         use self::prelude::*;
 
@@ -71,11 +92,36 @@ impl Note {
 
         result
     }
+
+    /// Transposes up by `interval`, moving the letter name by the interval's
+    /// generic number, so that C up a minor third is Eb rather than D#.
+    pub fn transpose(self, interval: Interval) -> Result<Self, UnspellableNote> {
+        let steps = self.note_name().steps_above_c() + (interval.number() - 1) % 7;
+        let pitch = (self.pitch() as i16 + interval.semitones()).rem_euclid(12);
+        Note::spell(NoteName::from_steps_above_c(steps), pitch as u8)
+    }
+
+    pub fn transpose_down(self, interval: Interval) -> Result<Self, UnspellableNote> {
+        let steps = self.note_name().steps_above_c() + 7 - (interval.number() - 1) % 7;
+        let pitch = (self.pitch() as i16 - interval.semitones()).rem_euclid(12);
+        Note::spell(NoteName::from_steps_above_c(steps), pitch as u8)
+    }
 }
 
-impl PartialEq for Note {
-    fn eq(&self, other: &Self) -> bool {
-        self.pitch() == other.pitch()
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub struct UnspellableNote {
+    pub note_name: NoteName,
+    pub pitch: u8,
+}
+
+impl fmt::Display for UnspellableNote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} can't be spelled as {} with at most a double accidental",
+            Note::from_pitch(self.pitch % 12).unwrap(),
+            self.note_name
+        )
     }
 }
 
@@ -119,38 +165,11 @@ impl fmt::Display for Note {
     }
 }
 
-#[derive(fmt::Debug, Clone, Eq, PartialEq)]
-pub struct UnrecognizedNote(String);
-
 impl FromStr for Note {
-    type Err = UnrecognizedNote;
+    type Err = ParseNoteError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_empty() {
-            Err(UnrecognizedNote(s.to_string()))
-        } else {
-            let note_name = match s.chars().next().unwrap().to_ascii_uppercase() {
-                'A' => NoteName::A,
-                'B' => NoteName::B,
-                'C' => NoteName::C,
-                'D' => NoteName::D,
-                'E' => NoteName::E,
-                'F' => NoteName::F,
-                'G' => NoteName::G,
-                _ => return Err(UnrecognizedNote(s.to_string())),
-            };
-
-            let accidental = match &s[1..] {
-                "bb" => Accidental::DoubleFlat,
-                "b" => Accidental::Flat,
-                "" => Accidental::Natural,
-                "#" => Accidental::Sharp,
-                "##" => Accidental::DoubleSharp,
-                _ => return Err(UnrecognizedNote(s.to_string())),
-            };
-
-            Ok(Note(note_name, accidental))
-        }
+        Note::parse_with(s, Notation::English)
     }
 }
 
@@ -162,6 +181,57 @@ mod tests {
     fn test_note_equality() {
         use self::Accidental::*;
         use self::NoteName::*;
-        assert_eq!(Note(D, Sharp), Note(E, Flat));
+        assert_ne!(Note(D, Sharp), Note(E, Flat));
+        assert!(Note(D, Sharp).is_enharmonic_to(Note(E, Flat)));
+        assert!(Note(B, Sharp).is_enharmonic_to(Note(D, DoubleFlat)));
+        assert!(!Note(C, Sharp).is_enharmonic_to(Note(D, Natural)));
+        assert_eq!(Note(F, Sharp), Note(F, Sharp));
+    }
+
+    #[test]
+    fn test_transpose() {
+        use self::Accidental::*;
+        use self::NoteName::*;
+
+        let transpose = |note: Note, interval: &str| {
+            let interval = interval.parse::<Interval>().unwrap();
+            note.transpose(interval)
+                .map(|note| (note.note_name(), note.accidental()))
+        };
+
+        assert_eq!(transpose(Note(C, Natural), "m3"), Ok((E, Flat)));
+        assert_eq!(transpose(Note(E, Flat), "m3"), Ok((G, Flat)));
+        assert_eq!(transpose(Note(F, Sharp), "d5"), Ok((C, Natural)));
+        assert_eq!(transpose(Note(C, Natural), "A2"), Ok((D, Sharp)));
+        assert_eq!(transpose(Note(B, Natural), "M3"), Ok((D, Sharp)));
+        assert_eq!(transpose(Note(D, Sharp), "M3"), Ok((F, DoubleSharp)));
+        assert_eq!(transpose(Note(G, Natural), "M10"), Ok((B, Natural)));
+        assert_eq!(transpose(Note(A, Flat), "P8"), Ok((A, Flat)));
+        assert_eq!(
+            transpose(Note(F, DoubleSharp), "A3"),
+            Err(UnspellableNote {
+                note_name: A,
+                pitch: Note(C, Natural).pitch()
+            })
+        );
+        assert_eq!(
+            UnspellableNote {
+                note_name: C,
+                pitch: 12
+            }
+            .to_string(),
+            "A can't be spelled as C with at most a double accidental"
+        );
+
+        let down = |note: Note, interval: &str| {
+            let interval = interval.parse::<Interval>().unwrap();
+            note.transpose_down(interval)
+                .map(|note| (note.note_name(), note.accidental()))
+        };
+
+        assert_eq!(down(Note(C, Natural), "m3"), Ok((A, Natural)));
+        assert_eq!(down(Note(C, Natural), "A4"), Ok((G, Flat)));
+        assert_eq!(down(Note(E, Flat), "M9"), Ok((D, Flat)));
+        assert_eq!(down(Note(D, Natural), "P1"), Ok((D, Natural)));
     }
 }
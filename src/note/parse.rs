@@ -0,0 +1,333 @@
+use std::fmt;
+
+use crate::accidental::Accidental;
+use crate::note::Note;
+use crate::note_name::NoteName;
+
+/// The naming system to read note names in.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Notation {
+    /// Letters A to G followed by accidentals: `#`, `b`, `x`, `♯`, `♭`, `𝄪`,
+    /// `𝄫` or `♮`.
+    #[default]
+    English,
+    /// Letters with H for B natural and B for B flat, and "is"/"es" suffixes
+    /// for sharps and flats (Cis, Es, Heses).
+    German,
+    /// Fixed-do solfège syllables (Do, Re, Mi, Fa, Sol, La, Si) followed by
+    /// English accidentals.
+    FixedDo,
+}
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseNoteErrorKind {
+    Empty,
+    InvalidNoteName,
+    InvalidAccidental,
+    MixedAccidentals,
+    AccidentalOutOfRange,
+}
+
+/// Why a note name couldn't be parsed, and the character position (not byte
+/// offset) at which the problem starts.
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct ParseNoteError {
+    pub kind: ParseNoteErrorKind,
+    pub position: usize,
+    pub input: String,
+}
+
+impl fmt::Display for ParseNoteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ParseNoteErrorKind::*;
+
+        if self.kind == Empty {
+            return write!(f, "empty note name");
+        }
+
+        let description = match self.kind {
+            Empty => unreachable!(),
+            InvalidNoteName => "unrecognized note name",
+            InvalidAccidental => "unrecognized accidental",
+            MixedAccidentals => "sharps mixed with flats",
+            AccidentalOutOfRange => "more than a double accidental",
+        };
+        write!(
+            f,
+            "{} at position {} in {:?}",
+            description, self.position, self.input
+        )
+    }
+}
+
+impl Note {
+    pub fn parse_with(s: &str, notation: Notation) -> Result<Self, ParseNoteError> {
+        let chars: Vec<char> = s.chars().collect();
+        let err = |kind, position| ParseNoteError {
+            kind,
+            position,
+            input: s.to_string(),
+        };
+
+        if chars.is_empty() {
+            return Err(err(ParseNoteErrorKind::Empty, 0));
+        }
+
+        let (note_name, accidental, len) = match notation {
+            Notation::English => {
+                let note_name = english_note_name(chars[0])
+                    .ok_or_else(|| err(ParseNoteErrorKind::InvalidNoteName, 0))?;
+                (note_name, 0, 1)
+            }
+            Notation::German => german_note_name(&chars)
+                .ok_or_else(|| err(ParseNoteErrorKind::InvalidNoteName, 0))?,
+            Notation::FixedDo => {
+                let (note_name, len) = solfege_note_name(&chars)
+                    .ok_or_else(|| err(ParseNoteErrorKind::InvalidNoteName, 0))?;
+                (note_name, 0, len)
+            }
+        };
+
+        let accidental = match notation {
+            Notation::German => accidental + german_accidentals(&chars, len, note_name, &err)?,
+            Notation::English | Notation::FixedDo => symbol_accidentals(&chars, len, &err)?,
+        };
+
+        match Accidental::from_interval(accidental) {
+            Some(accidental) => Ok(Note(note_name, accidental)),
+            None => Err(err(ParseNoteErrorKind::AccidentalOutOfRange, len)),
+        }
+    }
+}
+
+fn english_note_name(c: char) -> Option<NoteName> {
+    match c.to_ascii_uppercase() {
+        'A' => Some(NoteName::A),
+        'B' => Some(NoteName::B),
+        'C' => Some(NoteName::C),
+        'D' => Some(NoteName::D),
+        'E' => Some(NoteName::E),
+        'F' => Some(NoteName::F),
+        'G' => Some(NoteName::G),
+        _ => None,
+    }
+}
+
+// German B is B flat, so the name carries an accidental of its own.
+fn german_note_name(chars: &[char]) -> Option<(NoteName, i8, usize)> {
+    match chars[0].to_ascii_uppercase() {
+        'H' => Some((NoteName::B, 0, 1)),
+        'B' => Some((NoteName::B, -1, 1)),
+        'A' | 'C' | 'D' | 'E' | 'F' | 'G' => Some((english_note_name(chars[0])?, 0, 1)),
+        _ => None,
+    }
+}
+
+fn solfege_note_name(chars: &[char]) -> Option<(NoteName, usize)> {
+    const SYLLABLES: [(&str, NoteName); 9] = [
+        ("sol", NoteName::G),
+        ("so", NoteName::G),
+        ("do", NoteName::C),
+        ("re", NoteName::D),
+        ("mi", NoteName::E),
+        ("fa", NoteName::F),
+        ("la", NoteName::A),
+        ("si", NoteName::B),
+        ("ti", NoteName::B),
+    ];
+
+    let lower: String = chars.iter().take(3).collect::<String>().to_lowercase();
+    SYLLABLES
+        .iter()
+        .find(|(syllable, _)| lower.starts_with(syllable))
+        .map(|&(syllable, note_name)| (note_name, syllable.len()))
+}
+
+fn symbol_accidentals(
+    chars: &[char],
+    start: usize,
+    err: &impl Fn(ParseNoteErrorKind, usize) -> ParseNoteError,
+) -> Result<i8, ParseNoteError> {
+    let mut total = 0i8;
+
+    for (position, &c) in chars.iter().enumerate().skip(start) {
+        let step = match c {
+            '#' | '♯' => 1,
+            'b' | '♭' => -1,
+            'x' | '𝄪' => 2,
+            '𝄫' => -2,
+            '♮' if position == start && chars.len() == start + 1 => 0,
+            _ => return Err(err(ParseNoteErrorKind::InvalidAccidental, position)),
+        };
+
+        if total != 0 && (total > 0) != (step > 0) {
+            return Err(err(ParseNoteErrorKind::MixedAccidentals, position));
+        }
+        total = total.saturating_add(step);
+    }
+
+    Ok(total)
+}
+
+fn german_accidentals(
+    chars: &[char],
+    start: usize,
+    note_name: NoteName,
+    err: &impl Fn(ParseNoteErrorKind, usize) -> ParseNoteError,
+) -> Result<i8, ParseNoteError> {
+    let mut total = 0i8;
+    let mut position = start;
+
+    // As and Es drop the "e" of their first "es".
+    if matches!(note_name, NoteName::A | NoteName::E)
+        && chars.get(position).map(char::to_ascii_lowercase) == Some('s')
+    {
+        total = -1;
+        position += 1;
+    }
+
+    while position < chars.len() {
+        let suffix: String = chars[position..]
+            .iter()
+            .take(2)
+            .collect::<String>()
+            .to_lowercase();
+        let step = match suffix.as_str() {
+            "is" if note_name != NoteName::B || chars[0].eq_ignore_ascii_case(&'H') => 1,
+            "es" => -1,
+            _ => return Err(err(ParseNoteErrorKind::InvalidAccidental, position)),
+        };
+
+        if total != 0 && (total > 0) != (step > 0) {
+            return Err(err(ParseNoteErrorKind::MixedAccidentals, position));
+        }
+        total = total.saturating_add(step);
+        position += 2;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accidental::Accidental::*;
+    use crate::note_name::NoteName::*;
+
+    fn parse(s: &str, notation: Notation) -> Result<(NoteName, Accidental), ParseNoteErrorKind> {
+        Note::parse_with(s, notation)
+            .map(|note| (note.note_name(), note.accidental()))
+            .map_err(|e| e.kind)
+    }
+
+    #[test]
+    fn test_english() {
+        use Notation::English;
+
+        assert_eq!(parse("C", English), Ok((C, Natural)));
+        assert_eq!(parse("f#", English), Ok((F, Sharp)));
+        assert_eq!(parse("bb", English), Ok((B, Flat)));
+        assert_eq!(parse("Ebb", English), Ok((E, DoubleFlat)));
+        assert_eq!(parse("Gx", English), Ok((G, DoubleSharp)));
+        assert_eq!(parse("G##", English), Ok((G, DoubleSharp)));
+        assert_eq!(parse("D♭", English), Ok((D, Flat)));
+        assert_eq!(parse("A♯", English), Ok((A, Sharp)));
+        assert_eq!(parse("B𝄫", English), Ok((B, DoubleFlat)));
+        assert_eq!(parse("C𝄪", English), Ok((C, DoubleSharp)));
+        assert_eq!(parse("E♮", English), Ok((E, Natural)));
+
+        assert_eq!(parse("", English), Err(ParseNoteErrorKind::Empty));
+        assert_eq!(
+            parse("H", English),
+            Err(ParseNoteErrorKind::InvalidNoteName)
+        );
+        assert_eq!(
+            parse("C?", English),
+            Err(ParseNoteErrorKind::InvalidAccidental)
+        );
+        assert_eq!(
+            parse("C♮#", English),
+            Err(ParseNoteErrorKind::InvalidAccidental)
+        );
+        assert_eq!(
+            parse("C#b", English),
+            Err(ParseNoteErrorKind::MixedAccidentals)
+        );
+        assert_eq!(
+            parse("Cbbb", English),
+            Err(ParseNoteErrorKind::AccidentalOutOfRange)
+        );
+        assert_eq!(
+            parse("C#x", English),
+            Err(ParseNoteErrorKind::AccidentalOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_german() {
+        use Notation::German;
+
+        assert_eq!(parse("H", German), Ok((B, Natural)));
+        assert_eq!(parse("B", German), Ok((B, Flat)));
+        assert_eq!(parse("Heses", German), Ok((B, DoubleFlat)));
+        assert_eq!(parse("His", German), Ok((B, Sharp)));
+        assert_eq!(parse("Cis", German), Ok((C, Sharp)));
+        assert_eq!(parse("fisis", German), Ok((F, DoubleSharp)));
+        assert_eq!(parse("Des", German), Ok((D, Flat)));
+        assert_eq!(parse("Es", German), Ok((E, Flat)));
+        assert_eq!(parse("As", German), Ok((A, Flat)));
+        assert_eq!(parse("Ases", German), Ok((A, DoubleFlat)));
+        assert_eq!(parse("Eses", German), Ok((E, DoubleFlat)));
+
+        assert_eq!(
+            parse("Bis", German),
+            Err(ParseNoteErrorKind::InvalidAccidental)
+        );
+        assert_eq!(
+            parse("C#", German),
+            Err(ParseNoteErrorKind::InvalidAccidental)
+        );
+        assert_eq!(
+            parse("Cises", German),
+            Err(ParseNoteErrorKind::MixedAccidentals)
+        );
+        assert_eq!(
+            parse("Cisisis", German),
+            Err(ParseNoteErrorKind::AccidentalOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_fixed_do() {
+        use Notation::FixedDo;
+
+        assert_eq!(parse("Do", FixedDo), Ok((C, Natural)));
+        assert_eq!(parse("re#", FixedDo), Ok((D, Sharp)));
+        assert_eq!(parse("Mib", FixedDo), Ok((E, Flat)));
+        assert_eq!(parse("Sol", FixedDo), Ok((G, Natural)));
+        assert_eq!(parse("Sol♯", FixedDo), Ok((G, Sharp)));
+        assert_eq!(parse("La♭", FixedDo), Ok((A, Flat)));
+        assert_eq!(parse("Si", FixedDo), Ok((B, Natural)));
+        assert_eq!(parse("Ti", FixedDo), Ok((B, Natural)));
+
+        assert_eq!(
+            parse("C", FixedDo),
+            Err(ParseNoteErrorKind::InvalidNoteName)
+        );
+        assert_eq!(parse("Dox", FixedDo), Ok((C, DoubleSharp)));
+    }
+
+    #[test]
+    fn test_error_position() {
+        let e = Note::parse_with("F♯?", Notation::English).unwrap_err();
+        assert_eq!(e.kind, ParseNoteErrorKind::InvalidAccidental);
+        assert_eq!(e.position, 2);
+        assert_eq!(
+            e.to_string(),
+            "unrecognized accidental at position 2 in \"F♯?\""
+        );
+
+        let e = Note::parse_with("Fisse", Notation::German).unwrap_err();
+        assert_eq!(e.position, 3);
+    }
+}
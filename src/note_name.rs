@@ -1,6 +1,6 @@
 use std::fmt;
 
-#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[repr(u8)]
 pub enum NoteName {
     A = 0,
@@ -18,6 +18,36 @@ impl NoteName {
         self as u8
     }
 
+    pub const fn semitones_above_c(self) -> u8 {
+        (self.pitch() + 9) % 12
+    }
+
+    pub const fn steps_above_c(self) -> u8 {
+        use NoteName::*;
+        match self {
+            C => 0,
+            D => 1,
+            E => 2,
+            F => 3,
+            G => 4,
+            A => 5,
+            B => 6,
+        }
+    }
+
+    pub const fn from_steps_above_c(steps: u8) -> Self {
+        use NoteName::*;
+        match steps % 7 {
+            0 => C,
+            1 => D,
+            2 => E,
+            3 => F,
+            4 => G,
+            5 => A,
+            _ => B,
+        }
+    }
+
     pub const fn step_up(self) -> Self {
         use NoteName::*;
         match self {
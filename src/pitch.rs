@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+use std::{fmt, ops};
+
+use crate::interval::Interval;
+use crate::note::{Note, UnspellableNote};
+
+/// A note in a specific octave, in scientific pitch notation (C4 is middle C).
+///
+/// The octave number follows the letter name, so B#3 sounds the same as C4 and
+/// Cb4 sounds the same as B3.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Pitch(pub Note, pub i8);
+
+impl Pitch {
+    pub const fn new(note: Note, octave: i8) -> Self {
+        Pitch(note, octave)
+    }
+
+    /// The pitch `semitones` above C-1. Pitches outside the octaves an `i8`
+    /// can number are clamped to C-128 or B127.
+    pub fn from_semitones(semitones: i16) -> Self {
+        const LOWEST: i16 = (i8::MIN as i16 + 1) * 12;
+        const HIGHEST: i16 = (i8::MAX as i16 + 2) * 12 - 1;
+
+        let semitones = semitones.clamp(LOWEST, HIGHEST);
+        let octave = semitones.div_euclid(12) - 1;
+        let pitch_from_a = (semitones.rem_euclid(12) + 3) % 12;
+        Pitch(Note::from_pitch(pitch_from_a as u8).unwrap(), octave as i8)
+    }
+
+    pub fn from_midi(midi: u8) -> Option<Self> {
+        if midi <= 127 {
+            Some(Self::from_semitones(midi as i16))
+        } else {
+            None
+        }
+    }
+
+    pub const fn note(self) -> Note {
+        self.0
+    }
+
+    pub const fn octave(self) -> i8 {
+        self.1
+    }
+
+    /// Semitones above C-1, which is MIDI note number 0.
+    pub const fn semitones(self) -> i16 {
+        (self.octave() as i16 + 1) * 12
+            + self.note().note_name().semitones_above_c() as i16
+            + self.note().accidental().interval() as i16
+    }
+
+    /// Transposes up by `interval`, letter-correctly (see `Note::transpose`).
+    pub fn transpose(self, interval: Interval) -> Result<Self, UnspellableNote> {
+        let note = self.note().transpose(interval)?;
+        let steps = self.diatonic_steps() + interval.number() as i16 - 1;
+        Ok(Pitch(note, steps.div_euclid(7) as i8))
+    }
+
+    pub fn transpose_down(self, interval: Interval) -> Result<Self, UnspellableNote> {
+        let note = self.note().transpose_down(interval)?;
+        let steps = self.diatonic_steps() - interval.number() as i16 + 1;
+        Ok(Pitch(note, steps.div_euclid(7) as i8))
+    }
+
+    pub const fn is_enharmonic_to(self, other: Self) -> bool {
+        self.semitones() == other.semitones()
+    }
+
+    pub fn midi(self) -> Option<u8> {
+        match self.semitones() {
+            semitones @ 0..=127 => Some(semitones as u8),
+            _ => None,
+        }
+    }
+
+    /// Letter names above C0, ignoring accidentals.
+    pub const fn diatonic_steps(self) -> i16 {
+        self.octave() as i16 * 7 + self.note().note_name().steps_above_c() as i16
+    }
+}
+
+impl PartialOrd for Pitch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pitch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.semitones(), self.diatonic_steps()).cmp(&(other.semitones(), other.diatonic_steps()))
+    }
+}
+
+impl ops::Add<i8> for Pitch {
+    type Output = Pitch;
+
+    fn add(self, interval: i8) -> Self::Output {
+        Self::from_semitones(self.semitones() + interval as i16)
+    }
+}
+
+impl ops::Sub<i8> for Pitch {
+    type Output = Pitch;
+
+    fn sub(self, interval: i8) -> Self::Output {
+        self + -interval
+    }
+}
+
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.0, self.1)
+    }
+}
+
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct UnrecognizedPitch(pub(crate) String);
+
+impl FromStr for Pitch {
+    type Err = UnrecognizedPitch;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c.is_ascii_digit() || c == '-')
+            .map(|(i, _)| i)
+            .ok_or_else(|| UnrecognizedPitch(s.to_string()))?;
+
+        let note = s[..split]
+            .parse::<Note>()
+            .map_err(|_| UnrecognizedPitch(s.to_string()))?;
+        let octave = s[split..]
+            .parse::<i8>()
+            .map_err(|_| UnrecognizedPitch(s.to_string()))?;
+
+        Ok(Pitch(note, octave))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accidental::Accidental::*;
+    use crate::note_name::NoteName::*;
+
+    #[test]
+    fn test_midi() {
+        assert_eq!(Pitch(Note(C, Natural), 4).midi(), Some(60));
+        assert_eq!(Pitch(Note(A, Natural), 4).midi(), Some(69));
+        assert_eq!(Pitch(Note(C, Natural), -1).midi(), Some(0));
+        assert_eq!(Pitch(Note(B, Sharp), 3).midi(), Some(60));
+        assert_eq!(Pitch(Note(C, Flat), 4).midi(), Some(59));
+        assert_eq!(Pitch(Note(C, Flat), -1).midi(), None);
+        assert_eq!(Pitch(Note(G, Sharp), 9).midi(), None);
+
+        for midi in 0..=127 {
+            assert_eq!(Pitch::from_midi(midi).unwrap().midi(), Some(midi));
+        }
+        assert!(Pitch::from_midi(128).is_none());
+
+        assert_eq!(Pitch::from_semitones(-1524), Pitch(Note(C, Natural), -128));
+        assert_eq!(
+            Pitch::from_semitones(i16::MIN),
+            Pitch(Note(C, Natural), -128)
+        );
+        assert_eq!(Pitch::from_semitones(1547), Pitch(Note(B, Natural), 127));
+        assert_eq!(
+            Pitch::from_semitones(i16::MAX),
+            Pitch(Note(B, Natural), 127)
+        );
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in &["C#4", "Bb-1", "A0", "Ebb10", "G##-2"] {
+            assert_eq!(s.parse::<Pitch>().unwrap().to_string(), *s);
+        }
+
+        let pitch = "Bb-1".parse::<Pitch>().unwrap();
+        assert_eq!(
+            (pitch.note().note_name(), pitch.note().accidental()),
+            (B, Flat)
+        );
+        assert_eq!(pitch.octave(), -1);
+
+        for s in &["", "C", "4", "H4", "C#", "C4.5", "C--1"] {
+            assert!(s.parse::<Pitch>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut pitches: Vec<Pitch> = ["E4", "C4", "Bb3", "C5", "C#-1"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        pitches.sort();
+        let names: Vec<_> = pitches.iter().map(ToString::to_string).collect();
+        assert_eq!(names, vec!["C#-1", "Bb3", "C4", "E4", "C5"]);
+
+        assert!(Pitch(Note(B, Natural), 3) < Pitch(Note(C, Natural), 4));
+        assert!(Pitch(Note(C, Flat), 4) < Pitch(Note(C, Natural), 4));
+        assert!(Pitch(Note(B, Sharp), 3) < Pitch(Note(C, Natural), 4));
+        assert!(Pitch(Note(C, Natural), 4) < Pitch(Note(D, DoubleFlat), 4));
+        assert_ne!(Pitch(Note(B, Sharp), 3), Pitch(Note(C, Natural), 4));
+        assert!(Pitch(Note(B, Sharp), 3).is_enharmonic_to(Pitch(Note(C, Natural), 4)));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let transpose = |pitch: &str, interval: &str| {
+            let pitch = pitch.parse::<Pitch>().unwrap();
+            pitch
+                .transpose(interval.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(transpose("C4", "m3"), "Eb4");
+        assert_eq!(transpose("B3", "m2"), "C4");
+        assert_eq!(transpose("A3", "M10"), "C#5");
+        assert_eq!(transpose("Cb4", "P8"), "Cb5");
+        assert_eq!(transpose("B#3", "d2"), "C4");
+
+        let down = |pitch: &str, interval: &str| {
+            let pitch = pitch.parse::<Pitch>().unwrap();
+            pitch
+                .transpose_down(interval.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(down("C4", "m2"), "B3");
+        assert_eq!(down("E4", "A4"), "Bb3");
+        assert_eq!(down("D5", "P12"), "G3");
+    }
+}
@@ -0,0 +1,60 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::note::Note;
+
+pub mod set;
+
+/// Wraps a `Note` so that equality, hashing and ordering go by pitch class
+/// rather than spelling, e.g. for treating D# and Eb as the same key in a map.
+#[derive(fmt::Debug, Copy, Clone)]
+pub struct PitchClass(pub Note);
+
+impl PitchClass {
+    pub const fn note(self) -> Note {
+        self.0
+    }
+
+    pub const fn pitch(self) -> u8 {
+        self.0.pitch()
+    }
+}
+
+impl From<Note> for PitchClass {
+    fn from(note: Note) -> Self {
+        PitchClass(note)
+    }
+}
+
+impl PartialEq for PitchClass {
+    fn eq(&self, other: &Self) -> bool {
+        self.pitch() == other.pitch()
+    }
+}
+
+impl Eq for PitchClass {}
+
+impl Hash for PitchClass {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pitch().hash(state);
+    }
+}
+
+impl PartialOrd for PitchClass {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PitchClass {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.pitch().cmp(&other.pitch())
+    }
+}
+
+impl fmt::Display for PitchClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
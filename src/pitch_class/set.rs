@@ -0,0 +1,629 @@
+use std::fmt;
+
+use crate::chord::quality::ChordQuality;
+use crate::chord::registry::{ChordDefinition, DEFINITIONS};
+use crate::note::Note;
+use crate::scale::catalogue::FAMILIES;
+use crate::scale::Scale;
+
+/// A set of pitch classes numbered from C = 0, stored as twelve bits.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct PitchClassSet(u16);
+
+const ALL: u16 = 0xfff;
+
+impl PitchClassSet {
+    pub const CHROMATIC: PitchClassSet = PitchClassSet(ALL);
+    pub const EMPTY: PitchClassSet = PitchClassSet(0);
+
+    /// The set whose pitch class `n` is bit `n`. Bits above 11 are ignored.
+    pub const fn from_bits(bits: u16) -> Self {
+        PitchClassSet(bits & ALL)
+    }
+
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Collects pitch classes, reducing each modulo 12.
+    pub fn from_pitch_classes(pitch_classes: impl IntoIterator<Item = u8>) -> Self {
+        let mut set = PitchClassSet::EMPTY;
+        for pc in pitch_classes {
+            set.insert(pc);
+        }
+        set
+    }
+
+    pub fn from_notes(notes: &[Note]) -> Self {
+        // Note pitches count from A.
+        Self::from_pitch_classes(notes.iter().map(|note| (note.pitch() + 9) % 12))
+    }
+
+    /// The scale's notes with its root as 0.
+    pub fn from_scale(scale: &Scale) -> Self {
+        let mut pc = 0;
+        let mut set = PitchClassSet::EMPTY;
+        for interval in &scale.intervals {
+            set.insert(pc);
+            pc += interval.size();
+        }
+        set
+    }
+
+    /// The chord's tones with its root as 0.
+    pub fn from_quality(quality: &ChordQuality) -> Self {
+        Self::from_pitch_classes(
+            quality
+                .degrees()
+                .iter()
+                .map(|degree| degree.semitones().rem_euclid(12) as u8),
+        )
+    }
+
+    pub const fn contains(self, pc: u8) -> bool {
+        self.0 & (1 << (pc % 12)) != 0
+    }
+
+    pub fn insert(&mut self, pc: u8) {
+        self.0 |= 1 << (pc % 12);
+    }
+
+    pub fn remove(&mut self, pc: u8) {
+        self.0 &= !(1 << (pc % 12));
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The pitch classes in ascending order.
+    pub fn pitch_classes(self) -> Vec<u8> {
+        (0..12).filter(|&pc| self.contains(pc)).collect()
+    }
+
+    /// Tn: every pitch class raised by `n` semitones.
+    pub const fn transpose(self, n: u8) -> Self {
+        let n = n % 12;
+        PitchClassSet(((self.0 << n) | (self.0 >> (12 - n))) & ALL)
+    }
+
+    /// T0I: every pitch class `pc` becomes `-pc`.
+    pub fn invert(self) -> Self {
+        Self::from_pitch_classes(self.pitch_classes().into_iter().map(|pc| 12 - pc))
+    }
+
+    /// TnI: inversion followed by transposition by `n`.
+    pub fn transpose_inverted(self, n: u8) -> Self {
+        self.invert().transpose(n)
+    }
+
+    pub const fn complement(self) -> Self {
+        PitchClassSet(!self.0 & ALL)
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        PitchClassSet(self.0 | other.0)
+    }
+
+    pub const fn intersection(self, other: Self) -> Self {
+        PitchClassSet(self.0 & other.0)
+    }
+
+    pub const fn is_subset_of(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub const fn is_superset_of(self, other: Self) -> bool {
+        other.is_subset_of(self)
+    }
+
+    /// The `n` for which Tn takes this set to `other`, if there is one. A
+    /// symmetrical set gives the smallest.
+    pub fn transposition_to(self, other: Self) -> Option<u8> {
+        (0..12).find(|&n| self.transpose(n) == other)
+    }
+
+    /// Whether Tn or TnI takes this set to `other` for some `n`.
+    pub fn is_equivalent_to(self, other: Self) -> bool {
+        self.prime_form() == other.prime_form()
+    }
+
+    /// The most compact rotation of the pitch classes: the one spanning the
+    /// smallest interval, with ties broken by the interval from the first
+    /// pitch class to the second-last, then the third-last and so on, and
+    /// then by the lowest first pitch class.
+    pub fn normal_form(self) -> Vec<u8> {
+        let pcs = self.pitch_classes();
+        (0..pcs.len())
+            .map(|start| {
+                let mut rotation = pcs[start..].to_vec();
+                rotation.extend_from_slice(&pcs[..start]);
+                rotation
+            })
+            .min_by_key(|rotation| (packing(rotation), rotation[0]))
+            .unwrap_or_default()
+    }
+
+    /// The normal form of the set or of its inversion, whichever is more
+    /// compact, transposed to start on 0. Compactness is compared from the
+    /// right, as Rahn does, so a handful of sets such as 5-20 differ from the
+    /// prime forms in Forte's own table.
+    pub fn prime_form(self) -> Vec<u8> {
+        let zeroed = |set: PitchClassSet| {
+            let normal = set.normal_form();
+            let first = normal.first().cloned().unwrap_or(0);
+            normal
+                .iter()
+                .map(|pc| (pc + 12 - first) % 12)
+                .collect::<Vec<u8>>()
+        };
+
+        let original = zeroed(self);
+        let inverted = zeroed(self.invert());
+        std::cmp::min_by_key(original, inverted, |form| packing(form))
+    }
+
+    /// The prime form as a set.
+    pub fn prime(self) -> Self {
+        Self::from_pitch_classes(self.prime_form())
+    }
+
+    /// How many pairs of pitch classes lie each interval class apart, from
+    /// semitones to tritones.
+    pub fn interval_class_vector(self) -> [u8; 6] {
+        let pcs = self.pitch_classes();
+        let mut vector = [0; 6];
+        for (i, &a) in pcs.iter().enumerate() {
+            for &b in &pcs[i + 1..] {
+                let interval = b - a;
+                let class = interval.min(12 - interval);
+                vector[class as usize - 1] += 1;
+            }
+        }
+        vector
+    }
+
+    /// The set's Forte number, for sets of three to nine pitch classes.
+    pub fn forte_number(self) -> Option<ForteNumber> {
+        let prime = self.prime();
+        let (cardinality, complement) = match self.len() {
+            3..=6 => (self.len(), false),
+            7..=9 => (12 - self.len(), true),
+            _ => return None,
+        };
+        let target = if complement {
+            prime.complement().prime()
+        } else {
+            prime
+        };
+
+        let index = SET_CLASSES[cardinality - 3]
+            .iter()
+            .position(|&(form, _)| parse_form(form) == target)
+            .expect("every set class must be in the table");
+        Some(ForteNumber {
+            cardinality: self.len() as u8,
+            number: index as u8 + 1,
+            z: SET_CLASSES[cardinality - 3][index].1,
+        })
+    }
+
+    /// The prime form of the set's Z-correspondent: the other set class with
+    /// the same interval class vector, if there is one.
+    pub fn z_correspondent(self) -> Option<Self> {
+        let vector = self.interval_class_vector();
+        let prime = self.prime();
+        set_classes(self.len())
+            .into_iter()
+            .find(|&other| other != prime && other.interval_class_vector() == vector)
+    }
+
+    pub fn is_z_related_to(self, other: Self) -> bool {
+        self.z_correspondent() == Some(other.prime())
+    }
+
+    /// The catalogue scales and modes with exactly these pitch classes, each
+    /// with the pitch class of its root. A symmetrical scale is listed once
+    /// for each root it can have.
+    pub fn scales(self) -> Vec<(Scale, u8)> {
+        let mut seen = Vec::new();
+        let mut results = Vec::new();
+        for family in FAMILIES {
+            for rotation in 0..family.len() {
+                let scale = family.mode(rotation).unwrap();
+                let set = PitchClassSet::from_scale(&scale);
+                if seen.contains(&set) {
+                    continue;
+                }
+                seen.push(set);
+                for root in (0..12).filter(|&n| set.transpose(n) == self) {
+                    results.push((scale.clone(), root));
+                }
+            }
+        }
+        results
+    }
+
+    /// The registry chords with exactly these pitch classes, each with the
+    /// pitch class of its root.
+    pub fn chords(self) -> Vec<(&'static ChordDefinition, u8)> {
+        let mut results = Vec::new();
+        for definition in DEFINITIONS {
+            let set = PitchClassSet::from_quality(&definition.quality());
+            for root in (0..12).filter(|&n| set.transpose(n) == self) {
+                results.push((definition, root));
+            }
+        }
+        results
+    }
+}
+
+// Intervals from the first pitch class to each of the others, last first, so
+// that comparing them finds the form most packed to the left.
+fn packing(pcs: &[u8]) -> Vec<u8> {
+    let first = pcs.first().cloned().unwrap_or(0);
+    pcs.iter().rev().map(|pc| (pc + 12 - first) % 12).collect()
+}
+
+/// Writes the set as its pitch classes, with T and E for 10 and 11, e.g.
+/// "{0,4,7,T}".
+impl fmt::Display for PitchClassSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pcs: Vec<String> = self.pitch_classes().into_iter().map(pc_symbol).collect();
+        write!(f, "{{{}}}", pcs.join(","))
+    }
+}
+
+fn pc_symbol(pc: u8) -> String {
+    match pc {
+        10 => "T".to_string(),
+        11 => "E".to_string(),
+        _ => pc.to_string(),
+    }
+}
+
+/// A set class's name in Forte's catalogue, e.g. "4-Z15": its cardinality,
+/// its position in the list for that cardinality, and whether it has a
+/// Z-correspondent.
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ForteNumber {
+    pub cardinality: u8,
+    pub number: u8,
+    pub z: bool,
+}
+
+impl fmt::Display for ForteNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}{}",
+            self.cardinality,
+            if self.z { "Z" } else { "" },
+            self.number
+        )
+    }
+}
+
+fn parse_form(form: &str) -> PitchClassSet {
+    PitchClassSet::from_pitch_classes(form.chars().map(|c| match c {
+        'T' => 10,
+        'E' => 11,
+        _ => c.to_digit(10).unwrap() as u8,
+    }))
+}
+
+// The prime forms of every set class with `cardinality` pitch classes.
+fn set_classes(cardinality: usize) -> Vec<PitchClassSet> {
+    match cardinality {
+        3..=6 => SET_CLASSES[cardinality - 3]
+            .iter()
+            .map(|&(form, _)| parse_form(form))
+            .collect(),
+        7..=9 => set_classes(12 - cardinality)
+            .into_iter()
+            .map(|set| set.complement().prime())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Forte's set classes of three to six pitch classes, in his order, as Rahn
+// prime forms, with whether each is Z-related to another. Larger sets take
+// the number of their complement.
+static SET_CLASSES: [&[(&str, bool)]; 4] = [
+    &[
+        ("012", false),
+        ("013", false),
+        ("014", false),
+        ("015", false),
+        ("016", false),
+        ("024", false),
+        ("025", false),
+        ("026", false),
+        ("027", false),
+        ("036", false),
+        ("037", false),
+        ("048", false),
+    ],
+    &[
+        ("0123", false),
+        ("0124", false),
+        ("0134", false),
+        ("0125", false),
+        ("0126", false),
+        ("0127", false),
+        ("0145", false),
+        ("0156", false),
+        ("0167", false),
+        ("0235", false),
+        ("0135", false),
+        ("0236", false),
+        ("0136", false),
+        ("0237", false),
+        ("0146", true),
+        ("0157", false),
+        ("0347", false),
+        ("0147", false),
+        ("0148", false),
+        ("0158", false),
+        ("0246", false),
+        ("0247", false),
+        ("0257", false),
+        ("0248", false),
+        ("0268", false),
+        ("0358", false),
+        ("0258", false),
+        ("0369", false),
+        ("0137", true),
+    ],
+    &[
+        ("01234", false),
+        ("01235", false),
+        ("01245", false),
+        ("01236", false),
+        ("01237", false),
+        ("01256", false),
+        ("01267", false),
+        ("02346", false),
+        ("01246", false),
+        ("01346", false),
+        ("02347", false),
+        ("01356", true),
+        ("01248", false),
+        ("01257", false),
+        ("01268", false),
+        ("01347", false),
+        ("01348", true),
+        ("01457", true),
+        ("01367", false),
+        ("01568", false),
+        ("01458", false),
+        ("01478", false),
+        ("02357", false),
+        ("01357", false),
+        ("02358", false),
+        ("02458", false),
+        ("01358", false),
+        ("02368", false),
+        ("01368", false),
+        ("01468", false),
+        ("01369", false),
+        ("01469", false),
+        ("02468", false),
+        ("02469", false),
+        ("02479", false),
+        ("01247", true),
+        ("03458", true),
+        ("01258", true),
+    ],
+    &[
+        ("012345", false),
+        ("012346", false),
+        ("012356", true),
+        ("012456", true),
+        ("012367", false),
+        ("012567", true),
+        ("012678", false),
+        ("023457", false),
+        ("012357", false),
+        ("013457", true),
+        ("012457", true),
+        ("012467", true),
+        ("013467", true),
+        ("013458", false),
+        ("012458", false),
+        ("014568", false),
+        ("012478", true),
+        ("012578", false),
+        ("013478", true),
+        ("014589", false),
+        ("023468", false),
+        ("012468", false),
+        ("023568", true),
+        ("013468", true),
+        ("013568", true),
+        ("013578", true),
+        ("013469", false),
+        ("013569", true),
+        ("023679", true),
+        ("013679", false),
+        ("014579", false),
+        ("024579", false),
+        ("023579", false),
+        ("013579", false),
+        ("02468T", false),
+        ("012347", true),
+        ("012348", true),
+        ("012378", true),
+        ("023458", true),
+        ("012358", true),
+        ("012368", true),
+        ("012369", true),
+        ("012568", true),
+        ("012569", true),
+        ("023469", true),
+        ("012469", true),
+        ("012479", true),
+        ("012579", true),
+        ("013479", true),
+        ("014679", true),
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chord::quality::{sevenths, triads};
+    use crate::scale::catalogue;
+
+    fn set(pcs: &[u8]) -> PitchClassSet {
+        PitchClassSet::from_pitch_classes(pcs.iter().cloned())
+    }
+
+    fn notes(s: &str) -> Vec<Note> {
+        s.split_whitespace().map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_table() {
+        for &(cardinality, count) in [
+            (3, 12),
+            (4, 29),
+            (5, 38),
+            (6, 50),
+            (7, 38),
+            (8, 29),
+            (9, 12),
+        ]
+        .iter()
+        {
+            let classes = set_classes(cardinality);
+            assert_eq!(classes.len(), count, "{}", cardinality);
+            for (i, &class) in classes.iter().enumerate() {
+                assert_eq!(class.prime(), class, "{}-{}", cardinality, i + 1);
+                assert_eq!(class.len(), cardinality);
+                assert_eq!(class.forte_number().unwrap().number as usize, i + 1);
+                assert!(!classes[..i].contains(&class), "{}-{}", cardinality, i + 1);
+
+                // A set is marked Z exactly when another shares its vector.
+                let z = class.forte_number().unwrap().z;
+                assert_eq!(
+                    class.z_correspondent().is_some(),
+                    z,
+                    "{}-{}",
+                    cardinality,
+                    i + 1
+                );
+            }
+        }
+
+        // Every set of three to nine pitch classes is in the table.
+        for bits in 0..=ALL {
+            let set = PitchClassSet::from_bits(bits);
+            if (3..=9).contains(&set.len()) {
+                assert!(set_classes(set.len()).contains(&set.prime()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_forms() {
+        let c_major = PitchClassSet::from_notes(&notes("E G C"));
+        assert_eq!(c_major.to_string(), "{0,4,7}");
+        assert_eq!(c_major.normal_form(), [0, 4, 7]);
+        assert_eq!(c_major.prime_form(), [0, 3, 7]);
+        assert_eq!(c_major.forte_number().unwrap().to_string(), "3-11");
+        assert_eq!(c_major.interval_class_vector(), [0, 0, 1, 1, 1, 0]);
+
+        let set = set(&[11, 0, 2, 5]);
+        assert_eq!(set.normal_form(), [11, 0, 2, 5]);
+        assert_eq!(set.prime_form(), [0, 1, 3, 6]);
+        assert_eq!(set.forte_number().unwrap().to_string(), "4-13");
+
+        // Ties in span are broken from the right.
+        assert_eq!(
+            self::set(&[0, 1, 3, 4, 6, 9]).normal_form(),
+            [0, 1, 3, 4, 6, 9]
+        );
+        assert_eq!(self::set(&[1, 5, 7, 8]).normal_form(), [1, 5, 7, 8]);
+
+        assert_eq!(
+            self::set(&[0, 1, 5, 6, 8])
+                .forte_number()
+                .unwrap()
+                .to_string(),
+            "5-20"
+        );
+        assert_eq!(
+            PitchClassSet::from_notes(&notes("C D E F G A B"))
+                .forte_number()
+                .unwrap()
+                .to_string(),
+            "7-35"
+        );
+        assert_eq!(
+            PitchClassSet::from_notes(&notes("C Db")).forte_number(),
+            None
+        );
+        assert_eq!(PitchClassSet::EMPTY.normal_form(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_operations() {
+        let set = set(&[0, 1, 4]);
+        assert_eq!(set.transpose(11), self::set(&[11, 0, 3]));
+        assert_eq!(set.invert(), self::set(&[0, 11, 8]));
+        assert_eq!(set.transpose_inverted(4), self::set(&[4, 3, 0]));
+        assert_eq!(set.complement().len(), 9);
+        assert_eq!(set.complement().complement(), set);
+        assert!(set.is_subset_of(self::set(&[0, 1, 2, 4])));
+        assert!(!set.is_subset_of(self::set(&[0, 1, 2])));
+        assert!(self::set(&[0, 1, 2, 4]).is_superset_of(set));
+        assert_eq!(set.transposition_to(self::set(&[5, 6, 9])), Some(5));
+        assert_eq!(set.transposition_to(set.invert()), None);
+        assert!(set.is_equivalent_to(set.invert()));
+    }
+
+    #[test]
+    fn test_z_relation() {
+        let a = set(&[0, 1, 4, 6]);
+        let b = set(&[0, 1, 3, 7]);
+        assert_eq!(a.interval_class_vector(), [1, 1, 1, 1, 1, 1]);
+        assert!(a.is_z_related_to(b));
+        assert!(b.is_z_related_to(a.transpose(3)));
+        assert_eq!(a.forte_number().unwrap().to_string(), "4-Z15");
+        assert_eq!(set(&[0, 1, 2, 4]).z_correspondent(), None);
+    }
+
+    #[test]
+    fn test_scales_and_chords() {
+        let d_dorian = PitchClassSet::from_notes(&notes("D E F G A B C"));
+        let names: Vec<(String, u8)> = d_dorian
+            .scales()
+            .into_iter()
+            .map(|(scale, root)| (scale.name, root))
+            .collect();
+        assert_eq!(names[0], ("Major".to_string(), 0));
+        assert!(names.contains(&("Dorian".to_string(), 2)));
+        assert_eq!(names.len(), 7);
+
+        let whole_tone = PitchClassSet::from_scale(&catalogue::find("Whole Tone").unwrap());
+        assert_eq!(whole_tone.scales().len(), 6);
+
+        let chords = PitchClassSet::from_notes(&notes("A C E G")).chords();
+        let names: Vec<(&str, u8)> = chords.iter().map(|&(d, root)| (d.name, root)).collect();
+        assert_eq!(names, [("major sixth", 0), ("minor seventh", 9)]);
+
+        assert_eq!(
+            PitchClassSet::from_quality(&sevenths::DIMINISHED)
+                .chords()
+                .len(),
+            4
+        );
+        assert_eq!(PitchClassSet::from_quality(&triads::MAJOR), set(&[0, 4, 7]));
+    }
+}
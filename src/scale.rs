@@ -1,6 +1,12 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::degree::{flat, natural, Degree};
 use crate::interval::canonical::CanonicalInterval;
-use crate::note::Note;
+use crate::interval::Interval;
+use crate::note::{Note, UnspellableNote};
 
+pub mod catalogue;
 pub mod modal;
 pub mod mode;
 
@@ -8,6 +14,7 @@ pub mod mode;
 pub struct Scale {
     pub name: String,
     pub intervals: Vec<CanonicalInterval>,
+    spelling: Option<Vec<Degree>>,
 }
 
 impl Scale {
@@ -19,40 +26,440 @@ impl Scale {
                 .iter()
                 .map(|n| CanonicalInterval::from(*n))
                 .collect(),
+            spelling: None,
         }
     }
 
+    /// Spells the scale with the given degrees rather than by its default
+    /// convention, e.g. "1 b3 4 b5 5 b7" for a blues scale with a flat fifth.
+    /// The degrees must start on the root and agree with the intervals.
+    pub fn with_spelling(self, degrees: Vec<Degree>) -> Result<Self, InvalidSpelling> {
+        if degrees.len() != self.intervals.len() {
+            return Err(InvalidSpelling::Length {
+                expected: self.intervals.len(),
+                found: degrees.len(),
+            });
+        }
+
+        let mut semitones = 0;
+        let mut number = 1;
+        for (index, (&degree, interval)) in degrees.iter().zip(self.intervals.iter()).enumerate() {
+            let valid = if index == 0 {
+                degree.semitones() == 0 && degree.number() == 1
+            } else {
+                degree.semitones() == semitones && degree.number() >= number
+            };
+            if !valid {
+                return Err(InvalidSpelling::Degree { index, degree });
+            }
+            semitones += interval.size() as i16;
+            number = degree.number();
+        }
+
+        Ok(Scale {
+            spelling: Some(degrees),
+            ..self
+        })
+    }
+
     pub fn shift(&self, steps: usize) -> Self {
         let steps = steps % self.intervals.len();
+        let intervals: Vec<_> = self
+            .intervals
+            .iter()
+            .cloned()
+            .skip(steps)
+            .chain(self.intervals.iter().cloned().take(steps))
+            .collect();
+
+        let identified = catalogue::identify(&intervals);
+        let name = match identified {
+            Some((family, rotation)) => family.mode_name(rotation),
+            None => format!("{}(+{})", &self.name, steps),
+        };
+
+        // The catalogue's convention for the new mode beats a mechanical
+        // rotation of the old spelling, which can't tell that e.g. the
+        // octatonic scale alternates between two spellings.
+        let spelling = identified
+            .and_then(|(family, rotation)| family.spelling(rotation))
+            .or_else(|| {
+                self.spelling
+                    .as_ref()
+                    .and_then(|degrees| rotate_spelling(degrees, &self.intervals, steps))
+            });
+
         Scale {
-            // FIXME: Shifted scale names.
-            name: format!("{}(+{})", &self.name, steps),
-            intervals: self
-                .intervals
+            name,
+            intervals,
+            spelling,
+        }
+    }
+
+    /// The degrees the scale is spelled with: the hint given to
+    /// `with_spelling`, else the catalogue's convention, else for scales that
+    /// aren't heptatonic the most common degree for each semitone. Heptatonic
+    /// scales with no hint are spelled with each letter name once and have no
+    /// spelling here.
+    pub fn spelling(&self) -> Option<Vec<Degree>> {
+        if let Some(degrees) = &self.spelling {
+            return Some(degrees.clone());
+        }
+
+        if self.intervals.len() == 7 {
+            return None;
+        }
+
+        if let Some(degrees) = catalogue::identify(&self.intervals)
+            .and_then(|(family, rotation)| family.spelling(rotation))
+        {
+            return Some(degrees);
+        }
+
+        const DEFAULT_DEGREES: [Degree; 12] = [
+            natural(1),
+            flat(2),
+            natural(2),
+            flat(3),
+            natural(3),
+            natural(4),
+            flat(5),
+            natural(5),
+            flat(6),
+            natural(6),
+            flat(7),
+            natural(7),
+        ];
+        let mut semitones = 0;
+        let degrees = self.intervals.iter().map(|interval| {
+            let degree = DEFAULT_DEGREES[semitones % 12];
+            semitones += interval.size() as usize;
+            degree
+        });
+        Some(degrees.collect())
+    }
+
+    /// Spells the scale from `root`. Heptatonic scales use each letter name
+    /// exactly once and other scales follow their `spelling`. Any degree that
+    /// would need more than a double accidental is spelled enharmonically
+    /// instead (see `try_spell`).
+    pub fn spell(&self, root: Note) -> Vec<Note> {
+        self.spell_each(root)
+            .into_iter()
+            .map(|note| note.unwrap_or_else(|e| Note::from_pitch(e.pitch).unwrap()))
+            .collect()
+    }
+
+    /// Like `spell`, but fails rather than respelling a degree that would need
+    /// more than a double accidental.
+    pub fn try_spell(&self, root: Note) -> Result<Vec<Note>, UnspellableNote> {
+        self.spell_each(root).into_iter().collect()
+    }
+
+    pub(crate) fn spell_each(&self, root: Note) -> Vec<Result<Note, UnspellableNote>> {
+        match self.spelling() {
+            Some(degrees) => degrees
                 .iter()
-                .cloned()
-                .skip(steps)
-                .chain(self.intervals.iter().cloned().take(steps))
+                .map(|degree| root.transpose(degree.interval()))
                 .collect(),
+            None => self.spell_by_letter(root).collect(),
         }
     }
 
-    pub fn spell(&self, root: Note) -> Vec<Note> {
-        let mut result = vec![root];
+    fn spell_by_letter(
+        &self,
+        root: Note,
+    ) -> impl Iterator<Item = Result<Note, UnspellableNote>> + '_ {
+        let steps = self.intervals.iter().take(self.intervals.len() - 1);
+        let notes = steps.scan(
+            (root.note_name(), root.pitch()),
+            |(note_name, pitch), interval| {
+                *note_name = note_name.step_up();
+                *pitch = (*pitch + interval.size()) % 12;
+                Some(Note::spell(*note_name, *pitch))
+            },
+        );
 
-        let mut note = root;
+        Some(Ok(root)).into_iter().chain(notes)
+    }
+}
 
-        for interval in self.intervals.iter() {
-            let prev_note = note;
-            note = note + *interval;
-            if note.note_name() == prev_note.note_name() {
-                note = note.enharmonic();
+/// Rotates a spelling to start on its `steps`th degree, measuring each degree
+/// from the new root. Fails if a degree would need more than a double
+/// accidental.
+pub(crate) fn rotate_spelling(
+    degrees: &[Degree],
+    intervals: &[CanonicalInterval],
+    steps: usize,
+) -> Option<Vec<Degree>> {
+    let offsets: Vec<i16> = intervals
+        .iter()
+        .scan(0, |semitones, interval| {
+            let offset = *semitones;
+            *semitones += interval.size() as i16;
+            Some(offset)
+        })
+        .collect();
+
+    let root = degrees[steps];
+    (0..degrees.len())
+        .map(|i| {
+            let index = (steps + i) % degrees.len();
+            let octave = if index < steps { 7 } else { 0 };
+            let number = degrees[index].number() as i16 + octave - root.number() as i16 + 1;
+            let semitones = (offsets[index] - offsets[steps]).rem_euclid(12);
+            let interval =
+                Interval::from_number_and_semitones(u8::try_from(number).ok()?, semitones)?;
+            Degree::from_interval(interval)
+        })
+        .collect()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InvalidSpelling {
+    Length { expected: usize, found: usize },
+    Degree { index: usize, degree: Degree },
+}
+
+impl fmt::Display for InvalidSpelling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvalidSpelling::Length { expected, found } => {
+                write!(f, "expected {} degrees but found {}", expected, found)
             }
-            result.push(note);
+            InvalidSpelling::Degree { index, degree } => write!(
+                f,
+                "degree {} ({}) doesn't match the scale's intervals",
+                index + 1,
+                degree
+            ),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::{Key, KeySignature};
+    use crate::scale::catalogue;
+    use crate::scale::mode::Mode;
+
+    const MINOR_TONICS: [&str; 15] = [
+        "A", "E", "B", "F#", "C#", "G#", "D#", "A#", "D", "G", "C", "F", "Bb", "Eb", "Ab",
+    ];
+
+    fn spell(scale: &str, root: &str) -> String {
+        let notes = catalogue::find(scale).unwrap().spell(root.parse().unwrap());
+        notes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_harmonic_minor() {
+        let expected = [
+            "A B C D E F G#",
+            "E F# G A B C D#",
+            "B C# D E F# G A#",
+            "F# G# A B C# D E#",
+            "C# D# E F# G# A B#",
+            "G# A# B C# D# E F##",
+            "D# E# F# G# A# B C##",
+            "A# B# C# D# E# F# G##",
+            "D E F G A Bb C#",
+            "G A Bb C D Eb F#",
+            "C D Eb F G Ab B",
+            "F G Ab Bb C Db E",
+            "Bb C Db Eb F Gb A",
+            "Eb F Gb Ab Bb Cb D",
+            "Ab Bb Cb Db Eb Fb G",
+        ];
+
+        for (tonic, expected) in MINOR_TONICS.iter().zip(expected.iter()) {
+            assert_eq!(spell("Harmonic Minor", tonic), *expected);
+        }
+    }
 
-        result.pop().unwrap(); // Remove octave.
+    #[test]
+    fn test_melodic_minor() {
+        let expected = [
+            "A B C D E F# G#",
+            "E F# G A B C# D#",
+            "B C# D E F# G# A#",
+            "F# G# A B C# D# E#",
+            "C# D# E F# G# A# B#",
+            "G# A# B C# D# E# F##",
+            "D# E# F# G# A# B# C##",
+            "A# B# C# D# E# F## G##",
+            "D E F G A B C#",
+            "G A Bb C D E F#",
+            "C D Eb F G A B",
+            "F G Ab Bb C D E",
+            "Bb C Db Eb F G A",
+            "Eb F Gb Ab Bb C D",
+            "Ab Bb Cb Db Eb F G",
+        ];
+
+        for (tonic, expected) in MINOR_TONICS.iter().zip(expected.iter()) {
+            assert_eq!(spell("Melodic Minor", tonic), *expected);
+        }
+    }
+
+    #[test]
+    fn test_conventional_spellings() {
+        assert_eq!(spell("Blues", "C"), "C Eb F Gb G Bb");
+        assert_eq!(spell("Blues", "A"), "A C D Eb E G");
+        assert_eq!(spell("Major Blues", "C"), "C D Eb E G A");
+        assert_eq!(spell("Minor Pentatonic", "E"), "E G A B D");
+        assert_eq!(spell("Major Pentatonic", "F#"), "F# G# A# C# D#");
+        assert_eq!(spell("Hirajoshi", "A"), "A B C E F");
+        assert_eq!(spell("Whole Tone", "C"), "C D E F# G# Bb");
+        assert_eq!(spell("Augmented", "C"), "C Eb E G G# B");
+        assert_eq!(spell("Half-Whole Diminished", "C"), "C Db D# E F# G A Bb");
+        assert_eq!(spell("Whole-Half Diminished", "C"), "C D Eb F Gb Ab A B");
+        assert_eq!(spell("Bebop Dominant", "G"), "G A B C D E F F#");
+        assert_eq!(spell("Bebop Dorian", "D"), "D E F F# G A B C");
+
+        let octatonic = catalogue::find("Octatonic").unwrap();
+        assert_eq!(
+            octatonic.shift(1).spell("C".parse().unwrap()),
+            catalogue::find("Whole-Half Diminished")
+                .unwrap()
+                .spell("C".parse().unwrap())
+        );
+        assert_eq!(
+            catalogue::find("Blues")
+                .unwrap()
+                .shift(2)
+                .spelling()
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["1", "b2", "2", "4", "5", "b7"]
+        );
+
+        let custom = Scale::from_intervals("Custom", [1, 5, 6]);
+        assert_eq!(
+            custom
+                .spell("C".parse().unwrap())
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["C", "Db", "Gb"]
+        );
+    }
 
-        result
+    #[test]
+    fn test_spelling_hint() {
+        use crate::degree::parse_degrees;
+
+        let blues = catalogue::find("Blues").unwrap();
+        let sharp_four = blues
+            .clone()
+            .with_spelling(parse_degrees("1 b3 4 #4 5 b7").unwrap())
+            .unwrap();
+        let notes = sharp_four.spell("C".parse().unwrap());
+        assert_eq!(
+            notes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["C", "Eb", "F", "F#", "G", "Bb"]
+        );
+
+        assert_eq!(
+            blues
+                .clone()
+                .with_spelling(parse_degrees("1 b3 4 5 b7").unwrap())
+                .unwrap_err(),
+            InvalidSpelling::Length {
+                expected: 6,
+                found: 5
+            }
+        );
+        assert_eq!(
+            blues
+                .with_spelling(parse_degrees("1 b3 4 b5 #5 b7").unwrap())
+                .unwrap_err(),
+            InvalidSpelling::Degree {
+                index: 4,
+                degree: "#5".parse().unwrap()
+            }
+        );
+
+        // Hints take precedence over the letter-per-degree rule too.
+        let major = catalogue::find("Major")
+            .unwrap()
+            .with_spelling(parse_degrees("1 2 b4 4 5 6 7").unwrap())
+            .unwrap();
+        let notes = major.spell("C".parse().unwrap());
+        assert_eq!(
+            notes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["C", "D", "Fb", "F", "G", "A", "B"]
+        );
+        assert_eq!(spell("Major", "C"), "C D E F G A B");
+    }
+
+    #[test]
+    fn test_major_modes_match_key_signatures() {
+        // Every key with at most double accidentals in its tonic, including the
+        // theoretical ones beyond seven sharps or flats.
+        for fifths in -14..=14 {
+            for rotation in 0..7 {
+                let key = match Key::from_signature(KeySignature(fifths), Mode::new(rotation)) {
+                    Some(key) => key,
+                    None => continue,
+                };
+                // Keys spell themselves from their signature, independently of
+                // the scale.
+                match key.scale().to_scale().try_spell(key.tonic()) {
+                    Ok(notes) => assert_eq!(notes, key.spell(), "{}", key),
+                    Err(_) => assert!(fifths.abs() > 7, "{}", key),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_heptatonic_mode_from_every_tonic() {
+        use crate::accidental::Accidental::*;
+        use crate::note_name::NoteName::*;
+
+        let note_names = [C, D, E, F, G, A, B];
+        let accidentals = [DoubleFlat, Flat, Natural, Sharp, DoubleSharp];
+
+        for family in catalogue::FAMILIES
+            .iter()
+            .filter(|family| family.len() == 7)
+        {
+            for rotation in 0..7 {
+                let scale = family.mode(rotation).unwrap();
+
+                for &note_name in note_names.iter() {
+                    for &accidental in accidentals.iter() {
+                        let root = Note(note_name, accidental);
+                        let notes = match scale.try_spell(root) {
+                            Ok(notes) => notes,
+                            Err(_) => {
+                                // Only roots that are already altered can push a
+                                // degree past a double accidental.
+                                assert_ne!(accidental, Natural, "{} {}", root, scale.name);
+                                continue;
+                            }
+                        };
+                        assert_eq!(scale.spell(root), notes);
+
+                        let mut pitch = root.pitch();
+                        let mut letter = root.note_name();
+                        for (note, interval) in notes.iter().zip(scale.intervals.iter()) {
+                            assert_eq!(note.note_name(), letter, "{} {}", root, scale.name);
+                            assert_eq!(note.pitch(), pitch, "{} {}", root, scale.name);
+                            letter = letter.step_up();
+                            pitch = (pitch + interval.size()) % 12;
+                        }
+                    }
+                }
+            }
+        }
     }
 }
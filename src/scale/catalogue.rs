@@ -0,0 +1,424 @@
+use crate::degree::{self, Degree};
+use crate::interval::canonical::CanonicalInterval;
+use crate::scale::{self, Scale};
+
+/// A parent scale together with the conventional names of each of its
+/// rotations. The first name of each rotation is its primary name; the rest are
+/// aliases. A rotation with no names is referred to by its position, e.g.
+/// "Blues mode 3".
+///
+/// Families that aren't heptatonic also list conventional spellings as scale
+/// degrees, starting with the first rotation. Rotations past the end of the
+/// list are spelled by rotating the first spelling, unless the family repeats
+/// itself sooner, as the whole tone and octatonic scales do.
+#[derive(Debug)]
+pub struct ScaleFamily {
+    pub name: &'static str,
+    pub intervals: &'static [u8],
+    pub modes: &'static [&'static [&'static str]],
+    pub spellings: &'static [&'static str],
+}
+
+const WHOLE_TONE: &[&str] = &["Whole Tone"];
+const CHROMATIC: &[&str] = &["Chromatic"];
+
+pub static FAMILIES: &[ScaleFamily] = &[
+    ScaleFamily {
+        name: "Major",
+        intervals: &[2, 2, 1, 2, 2, 2, 1],
+        modes: &[
+            &["Major", "Ionian"],
+            &["Dorian"],
+            &["Phrygian"],
+            &["Lydian"],
+            &["Mixolydian", "Dominant"],
+            &["Natural Minor", "Aeolian", "Minor"],
+            &["Locrian"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Melodic Minor",
+        intervals: &[2, 1, 2, 2, 2, 2, 1],
+        modes: &[
+            &["Melodic Minor", "Jazz Minor", "Ascending Melodic Minor"],
+            &["Dorian ♭2", "Phrygian ♮6"],
+            &["Lydian Augmented", "Lydian ♯5"],
+            &["Lydian Dominant", "Overtone", "Acoustic", "Lydian ♭7"],
+            &["Mixolydian ♭6", "Aeolian Dominant", "Hindu"],
+            &["Locrian ♮2", "Half-Diminished", "Locrian ♯2", "Aeolian ♭5"],
+            &["Altered", "Super Locrian", "Altered Dominant"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Harmonic Minor",
+        intervals: &[2, 1, 2, 2, 1, 3, 1],
+        modes: &[
+            &["Harmonic Minor", "Aeolian ♮7"],
+            &["Locrian ♮6"],
+            &["Ionian ♯5", "Ionian Augmented"],
+            &["Dorian ♯4", "Ukrainian Dorian", "Romanian Minor"],
+            &[
+                "Phrygian Dominant",
+                "Spanish Gypsy",
+                "Freygish",
+                "Phrygian ♮3",
+            ],
+            &["Lydian ♯2"],
+            &["Altered Diminished", "Ultralocrian", "Super Locrian 𝄫7"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Harmonic Major",
+        intervals: &[2, 2, 1, 2, 1, 3, 1],
+        modes: &[
+            &["Harmonic Major", "Ionian ♭6"],
+            &["Dorian ♭5"],
+            &["Phrygian ♭4"],
+            &["Lydian ♭3"],
+            &["Mixolydian ♭2"],
+            &["Lydian Augmented ♯2"],
+            &["Locrian 𝄫7"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Double Harmonic Major",
+        intervals: &[1, 3, 1, 2, 1, 3, 1],
+        modes: &[
+            &[
+                "Double Harmonic Major",
+                "Double Harmonic",
+                "Byzantine",
+                "Arabic",
+                "Gypsy Major",
+            ],
+            &["Lydian ♯2 ♯6"],
+            &["Ultraphrygian"],
+            &["Hungarian Minor", "Double Harmonic Minor", "Gypsy Minor"],
+            &["Oriental"],
+            &["Ionian ♯2 ♯5"],
+            &["Locrian 𝄫3 𝄫7"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Neapolitan Major",
+        intervals: &[1, 2, 2, 2, 2, 2, 1],
+        modes: &[
+            &["Neapolitan Major"],
+            &["Leading Whole-Tone"],
+            &["Lydian Augmented Dominant"],
+            &["Lydian Minor"],
+            &["Major Locrian", "Arabian"],
+            &["Half-Diminished ♭4"],
+            &["Super Locrian 𝄫3"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Neapolitan Minor",
+        intervals: &[1, 2, 2, 2, 1, 3, 1],
+        modes: &[
+            &["Neapolitan Minor"],
+            &["Lydian ♯6"],
+            &["Mixolydian Augmented"],
+            &["Hungarian Gypsy", "Aeolian ♯4"],
+            &["Locrian Dominant"],
+            &["Ionian ♯2"],
+            &["Ultralocrian 𝄫3"],
+        ],
+        spellings: &[],
+    },
+    ScaleFamily {
+        name: "Major Pentatonic",
+        intervals: &[2, 2, 3, 2, 3],
+        modes: &[
+            &["Major Pentatonic", "Pentatonic"],
+            &["Suspended Pentatonic", "Egyptian"],
+            &["Blues Minor Pentatonic", "Man Gong"],
+            &["Blues Major Pentatonic", "Ritsusen", "Yo"],
+            &["Minor Pentatonic"],
+        ],
+        spellings: &["1 2 3 5 6"],
+    },
+    ScaleFamily {
+        name: "Hirajoshi",
+        intervals: &[2, 1, 4, 1, 4],
+        modes: &[&["Hirajoshi"], &["Iwato"], &[], &[], &[]],
+        spellings: &["1 2 b3 5 b6"],
+    },
+    ScaleFamily {
+        name: "Kumoi",
+        intervals: &[2, 1, 4, 2, 3],
+        modes: &[&["Kumoi"], &["In Sen"], &[], &[], &[]],
+        spellings: &["1 2 b3 5 6"],
+    },
+    ScaleFamily {
+        name: "Blues",
+        intervals: &[3, 2, 1, 1, 3, 2],
+        modes: &[
+            &["Blues", "Minor Blues"],
+            &["Major Blues"],
+            &[],
+            &[],
+            &[],
+            &[],
+        ],
+        spellings: &["1 b3 4 b5 5 b7"],
+    },
+    ScaleFamily {
+        name: "Whole Tone",
+        intervals: &[2, 2, 2, 2, 2, 2],
+        modes: &[WHOLE_TONE; 6],
+        spellings: &["1 2 3 #4 #5 b7"],
+    },
+    ScaleFamily {
+        name: "Augmented",
+        intervals: &[3, 1, 3, 1, 3, 1],
+        modes: &[
+            &["Augmented"],
+            &["Six-Tone Symmetrical"],
+            &["Augmented"],
+            &["Six-Tone Symmetrical"],
+            &["Augmented"],
+            &["Six-Tone Symmetrical"],
+        ],
+        spellings: &["1 b3 3 5 #5 7", "1 b2 3 4 #5 6"],
+    },
+    ScaleFamily {
+        name: "Octatonic",
+        intervals: &[1, 2, 1, 2, 1, 2, 1, 2],
+        modes: &[
+            &["Half-Whole Diminished", "Dominant Diminished", "Octatonic"],
+            &["Whole-Half Diminished", "Diminished"],
+            &["Half-Whole Diminished"],
+            &["Whole-Half Diminished"],
+            &["Half-Whole Diminished"],
+            &["Whole-Half Diminished"],
+            &["Half-Whole Diminished"],
+            &["Whole-Half Diminished"],
+        ],
+        spellings: &["1 b2 #2 3 #4 5 6 b7", "1 2 b3 4 b5 b6 6 7"],
+    },
+    ScaleFamily {
+        name: "Bebop Dominant",
+        intervals: &[2, 2, 1, 2, 2, 1, 1, 1],
+        modes: &[
+            &["Bebop Dominant"],
+            &[],
+            &[],
+            &[],
+            &["Bebop Dorian", "Bebop Minor"],
+            &[],
+            &[],
+            &[],
+        ],
+        spellings: &["1 2 3 4 5 6 b7 7"],
+    },
+    ScaleFamily {
+        name: "Bebop Major",
+        intervals: &[2, 2, 1, 2, 1, 1, 2, 1],
+        modes: &[&["Bebop Major"], &[], &[], &[], &[], &[], &[], &[]],
+        spellings: &["1 2 3 4 5 #5 6 7"],
+    },
+    ScaleFamily {
+        name: "Bebop Melodic Minor",
+        intervals: &[2, 1, 2, 2, 1, 1, 2, 1],
+        modes: &[&["Bebop Melodic Minor"], &[], &[], &[], &[], &[], &[], &[]],
+        spellings: &["1 2 b3 4 5 #5 6 7"],
+    },
+    ScaleFamily {
+        name: "Chromatic",
+        intervals: &[1; 12],
+        modes: &[CHROMATIC; 12],
+        spellings: &["1 b2 2 b3 3 4 #4 5 b6 6 b7 7"],
+    },
+];
+
+impl ScaleFamily {
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn mode_name(&self, rotation: usize) -> String {
+        match self.modes[rotation].first() {
+            Some(name) => name.to_string(),
+            None => format!("{} mode {}", self.name, rotation + 1),
+        }
+    }
+
+    pub fn aliases(&self, rotation: usize) -> &'static [&'static str] {
+        self.modes[rotation].get(1..).unwrap_or(&[])
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.mode(0).unwrap()
+    }
+
+    pub fn mode(&self, rotation: usize) -> Option<Scale> {
+        if rotation >= self.len() {
+            return None;
+        }
+
+        let intervals = self.intervals[rotation..]
+            .iter()
+            .chain(&self.intervals[..rotation]);
+        let scale = Scale::from_intervals(
+            self.mode_name(rotation),
+            intervals.cloned().collect::<Vec<_>>(),
+        );
+
+        match self.spelling(rotation) {
+            Some(degrees) => Some(
+                scale
+                    .with_spelling(degrees)
+                    .expect("a catalogue spelling must match its intervals"),
+            ),
+            None => Some(scale),
+        }
+    }
+
+    /// The conventional spelling of a rotation, if the family has one.
+    pub fn spelling(&self, rotation: usize) -> Option<Vec<Degree>> {
+        let parse = |s| degree::parse_degrees(s).expect("catalogue spellings must parse");
+        let first = parse(self.spellings.first()?);
+
+        let len = self.len();
+        let period = (1..=len)
+            .find(|&p| (0..len).all(|i| self.intervals[i] == self.intervals[(i + p) % len]))
+            .unwrap();
+
+        match self.spellings.get(rotation % period) {
+            Some(spelling) => Some(parse(spelling)),
+            None => {
+                let intervals = Scale::from_intervals(self.name, self.intervals).intervals;
+                scale::rotate_spelling(&first, &intervals, rotation)
+            }
+        }
+    }
+}
+
+/// Looks up a scale by any of its names or aliases, ignoring case, spacing and
+/// the difference between e.g. "♭" and "b".
+pub fn find(name: &str) -> Option<Scale> {
+    find_mode(name).and_then(|(family, rotation)| family.mode(rotation))
+}
+
+pub fn find_mode(name: &str) -> Option<(&'static ScaleFamily, usize)> {
+    let name = normalize(name);
+    FAMILIES.iter().find_map(|family| {
+        family
+            .modes
+            .iter()
+            .position(|names| names.iter().any(|n| normalize(n) == name))
+            .map(|rotation| (family, rotation))
+    })
+}
+
+/// Finds the family and rotation with exactly these intervals.
+pub fn identify(intervals: &[CanonicalInterval]) -> Option<(&'static ScaleFamily, usize)> {
+    FAMILIES.iter().find_map(|family| {
+        (0..family.len())
+            .find(|&rotation| {
+                family.len() == intervals.len()
+                    && intervals.iter().enumerate().all(|(i, interval)| {
+                        interval.size() == family.intervals[(rotation + i) % family.len()]
+                    })
+            })
+            .map(|rotation| (family, rotation))
+    })
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .replace('♭', "b")
+        .replace('♯', "#")
+        .replace('𝄫', "bb")
+        .replace('𝄪', "x")
+        .replace("natural", "♮")
+        .replace("nat", "♮")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_families() {
+        for family in FAMILIES {
+            assert_eq!(family.intervals.iter().sum::<u8>(), 12, "{}", family.name);
+            assert_eq!(family.modes.len(), family.len(), "{}", family.name);
+            let (found, rotation) = find_mode(family.name).unwrap();
+            assert!(std::ptr::eq(found, family), "{}", family.name);
+            assert_eq!(rotation, 0);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let intervals = |name: &str| -> Vec<u8> {
+            find(name)
+                .unwrap()
+                .intervals
+                .iter()
+                .map(|interval| interval.size())
+                .collect()
+        };
+
+        assert_eq!(intervals("Major"), [2, 2, 1, 2, 2, 2, 1]);
+        assert_eq!(intervals("ionian"), [2, 2, 1, 2, 2, 2, 1]);
+        assert_eq!(intervals("Minor"), [2, 1, 2, 2, 1, 2, 2]);
+        assert_eq!(intervals("Altered"), [1, 2, 1, 2, 2, 2, 2]);
+        assert_eq!(intervals("Super Locrian"), [1, 2, 1, 2, 2, 2, 2]);
+        assert_eq!(intervals("Lydian Dominant"), [2, 2, 2, 1, 2, 1, 2]);
+        assert_eq!(intervals("Phrygian Dominant"), [1, 3, 1, 2, 1, 2, 2]);
+        assert_eq!(intervals("Hungarian Minor"), [2, 1, 3, 1, 1, 3, 1]);
+        assert_eq!(intervals("Minor Pentatonic"), [3, 2, 2, 3, 2]);
+        assert_eq!(intervals("Major Blues"), [2, 1, 1, 3, 2, 3]);
+        assert_eq!(intervals("Whole-Half Diminished"), [2, 1, 2, 1, 2, 1, 2, 1]);
+        assert_eq!(intervals("Bebop Dorian"), [2, 1, 1, 1, 2, 2, 1, 2]);
+
+        assert_eq!(find("locrian natural 2").unwrap().name, "Locrian ♮2");
+        assert_eq!(find("Locrian nat2").unwrap().name, "Locrian ♮2");
+        assert_eq!(find("dorian b2").unwrap().name, "Dorian ♭2");
+        assert_eq!(find("Altered Dominant").unwrap().name, "Altered");
+        assert_eq!(find("Locrian #2").unwrap().name, "Locrian ♮2");
+        assert!(find("Nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_shift_names() {
+        let major = find("Major").unwrap();
+        assert_eq!(major.shift(3).name, "Lydian");
+        assert_eq!(major.shift(5).name, "Natural Minor");
+
+        let melodic_minor = find("Melodic Minor").unwrap();
+        assert_eq!(melodic_minor.shift(3).name, "Lydian Dominant");
+        assert_eq!(melodic_minor.shift(5).name, "Locrian ♮2");
+        assert_eq!(melodic_minor.shift(6).name, "Altered");
+
+        assert_eq!(
+            find("Harmonic Minor").unwrap().shift(4).name,
+            "Phrygian Dominant"
+        );
+        assert_eq!(find("Blues").unwrap().shift(2).name, "Blues mode 3");
+        assert_eq!(find("Whole Tone").unwrap().shift(1).name, "Whole Tone");
+        assert_eq!(
+            find("Octatonic").unwrap().shift(1).name,
+            "Whole-Half Diminished"
+        );
+
+        let custom = Scale::from_intervals("Custom", [1, 1, 10]);
+        assert_eq!(custom.shift(1).name, "Custom(+1)");
+    }
+}
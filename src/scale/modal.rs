@@ -8,12 +8,31 @@ pub struct ModalScale {
 }
 
 impl ModalScale {
-    pub fn new(scale: Scale, mode: Mode) -> Self {
-        ModalScale { scale, mode }
+    /// Pairs `scale` with one of its modes, or returns `None` if the scale has
+    /// too few notes for that rotation.
+    pub fn new(scale: Scale, mode: Mode) -> Option<Self> {
+        if mode.rotation() < scale.intervals.len() {
+            Some(ModalScale { scale, mode })
+        } else {
+            None
+        }
+    }
+
+    pub fn modes(scale: &Scale) -> impl Iterator<Item = ModalScale> + '_ {
+        (0..scale.intervals.len())
+            .map(move |rotation| ModalScale::new(scale.clone(), Mode::new(rotation as u8)).unwrap())
+    }
+
+    pub fn name(&self) -> String {
+        self.mode.name(&self.scale).unwrap()
+    }
+
+    pub fn to_scale(&self) -> Scale {
+        self.scale.shift(self.mode.rotation())
     }
 
     pub fn spell(&self, root: Note) -> Vec<Note> {
-        self.scale.shift(self.mode as u8 as usize).spell(root)
+        self.to_scale().spell(root)
     }
 }
 
@@ -27,12 +46,13 @@ mod tests {
         use crate::note_name::NoteName::*;
 
         let minor_scale = ModalScale::new(
-            Scale::from_intervals("Major", &[2, 2, 1, 2, 2, 2, 1]),
-            Mode::Aeolian,
-        );
+            Scale::from_intervals("Major", [2, 2, 1, 2, 2, 2, 1]),
+            Mode::AEOLIAN,
+        )
+        .unwrap();
 
         let notes = minor_scale.spell(Note(F, Sharp));
-        let expected_notes = vec![
+        let expected_notes = [
             Note(F, Sharp),
             Note(G, Sharp),
             Note(A, Natural),
@@ -54,4 +74,42 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_modes_of_any_scale() {
+        use crate::accidental::Accidental::*;
+        use crate::note_name::NoteName::*;
+        use crate::scale::catalogue;
+
+        let harmonic_minor = catalogue::find("Harmonic Minor").unwrap();
+        let phrygian_dominant = ModalScale::new(harmonic_minor.clone(), Mode::new(4)).unwrap();
+        assert_eq!(phrygian_dominant.name(), "Phrygian Dominant");
+        assert_eq!(
+            phrygian_dominant.spell(Note(E, Natural)),
+            [
+                Note(E, Natural),
+                Note(F, Natural),
+                Note(G, Sharp),
+                Note(A, Natural),
+                Note(B, Natural),
+                Note(C, Natural),
+                Note(D, Natural),
+            ]
+        );
+        assert!(ModalScale::new(harmonic_minor, Mode::new(7)).is_none());
+
+        let pentatonic = catalogue::find("Major Pentatonic").unwrap();
+        let names: Vec<_> = ModalScale::modes(&pentatonic).map(|m| m.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "Major Pentatonic",
+                "Suspended Pentatonic",
+                "Blues Minor Pentatonic",
+                "Blues Major Pentatonic",
+                "Minor Pentatonic",
+            ]
+        );
+        assert!(ModalScale::new(pentatonic, Mode::LOCRIAN).is_none());
+    }
 }
@@ -1,28 +1,41 @@
-#[derive(Debug, Copy, Clone)]
-#[repr(u8)]
-pub enum Mode {
-    Ionian = 0,
-    Dorian,
-    Phrygian,
-    Lydian,
-    Mixolydian,
-    Aeolian,
-    Locrian,
+use crate::scale::Scale;
+
+/// A rotation of a parent scale, counted from zero. The named constants are the
+/// modes of the major scale, but a mode can index into any `Scale`; whether it
+/// fits is checked when it's paired with one in a `ModalScale`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Mode(u8);
+
+impl Mode {
+    pub const AEOLIAN: Mode = Mode(5);
+    pub const DORIAN: Mode = Mode(1);
+    pub const IONIAN: Mode = Mode(0);
+    pub const LOCRIAN: Mode = Mode(6);
+    pub const LYDIAN: Mode = Mode(3);
+    pub const MIXOLYDIAN: Mode = Mode(4);
+    pub const PHRYGIAN: Mode = Mode(2);
+
+    pub const fn new(rotation: u8) -> Self {
+        Mode(rotation)
+    }
+
+    pub const fn rotation(self) -> usize {
+        self.0 as usize
+    }
+
+    /// The name of this rotation of `scale`, from the scale catalogue where
+    /// possible, or `None` if the scale doesn't have that many notes.
+    pub fn name(self, scale: &Scale) -> Option<String> {
+        if self.rotation() < scale.intervals.len() {
+            Some(scale.shift(self.rotation()).name)
+        } else {
+            None
+        }
+    }
 }
 
 impl From<u8> for Mode {
-    fn from(value: u8) -> Self {
-        use Mode::*;
-        match value {
-            0 => Ionian,
-            1 => Dorian,
-            2 => Phrygian,
-            3 => Lydian,
-            4 => Mixolydian,
-            5 => Aeolian,
-            6 => Locrian,
-            value if value >= 7 => Mode::from(value - 7),
-            _ => unreachable!(),
-        }
+    fn from(rotation: u8) -> Self {
+        Mode(rotation)
     }
 }
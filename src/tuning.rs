@@ -0,0 +1,137 @@
+use crate::pitch::Pitch;
+
+pub mod scala;
+pub mod temperament;
+
+/// The usual frequency of A4, in hertz.
+pub const CONCERT_A: f64 = 440.0;
+
+/// A4 counted in semitones above C-1, the way `Pitch::semitones` counts.
+const A4_SEMITONES: i16 = 69;
+
+/// Maps pitches to the frequencies they sound at.
+pub trait Tuning {
+    /// The frequency of `pitch` in hertz.
+    fn frequency(&self, pitch: Pitch) -> f64;
+
+    /// The pitch that sounds closest to `frequency`, and how many cents
+    /// `frequency` lies above it (below, if negative). Both spellings of each
+    /// nearby pitch are tried, since some tunings tell them apart.
+    fn nearest(&self, frequency: f64) -> (Pitch, f64) {
+        let a4 = self.frequency(Pitch::from_semitones(A4_SEMITONES));
+        let estimate = A4_SEMITONES + (12.0 * (frequency / a4).log2()).round() as i16;
+
+        (estimate - 1..=estimate + 1)
+            .flat_map(|semitones| {
+                let pitch = Pitch::from_semitones(semitones);
+                vec![pitch, enharmonic(pitch)]
+            })
+            .map(|pitch| (pitch, cents(frequency / self.frequency(pitch))))
+            .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+            .unwrap()
+    }
+}
+
+/// The size of a frequency ratio in cents, hundredths of an equal-tempered
+/// semitone.
+pub fn cents(ratio: f64) -> f64 {
+    1200.0 * ratio.log2()
+}
+
+/// The frequency ratio spanning `cents`.
+pub fn ratio(cents: f64) -> f64 {
+    2f64.powf(cents / 1200.0)
+}
+
+// The same pitch spelled the other way, e.g. Db4 for C#4 and C4 for B#3.
+fn enharmonic(pitch: Pitch) -> Pitch {
+    let note = pitch.note().enharmonic();
+    let mut result = Pitch(note, pitch.octave());
+    while result.semitones() < pitch.semitones() {
+        result.1 += 1;
+    }
+    while result.semitones() > pitch.semitones() {
+        result.1 -= 1;
+    }
+    result
+}
+
+/// Twelve-tone equal temperament, with A4 at `reference` hertz.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EqualTemperament {
+    pub reference: f64,
+}
+
+impl EqualTemperament {
+    pub const fn new(reference: f64) -> Self {
+        EqualTemperament { reference }
+    }
+
+    fn frequency_of_semitones(self, semitones: i16) -> f64 {
+        self.reference * ratio(100.0 * (semitones - A4_SEMITONES) as f64)
+    }
+}
+
+impl Default for EqualTemperament {
+    fn default() -> Self {
+        EqualTemperament::new(CONCERT_A)
+    }
+}
+
+impl Tuning for EqualTemperament {
+    fn frequency(&self, pitch: Pitch) -> f64 {
+        self.frequency_of_semitones(pitch.semitones())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn pitch(s: &str) -> Pitch {
+        s.parse().unwrap()
+    }
+
+    pub(crate) fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_equal_temperament() {
+        let tuning = EqualTemperament::default();
+        assert_close(tuning.frequency(pitch("A4")), 440.0);
+        assert_close(tuning.frequency(pitch("A5")), 880.0);
+        assert_close(tuning.frequency(pitch("C4")), 261.63);
+        assert_close(tuning.frequency(pitch("B#3")), 261.63);
+        assert_close(tuning.frequency(pitch("C-1")), 8.18);
+
+        let baroque = EqualTemperament::new(415.0);
+        assert_close(baroque.frequency(pitch("A3")), 207.5);
+        assert_close(baroque.frequency(pitch("Bb3")), 219.84);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let tuning = EqualTemperament::default();
+
+        let (pitch, offset) = tuning.nearest(445.0);
+        assert_eq!(pitch, self::pitch("A4"));
+        assert_close(offset, 19.56);
+
+        let (pitch, offset) = tuning.nearest(255.0);
+        assert_eq!(pitch, self::pitch("C4"));
+        assert_close(offset, -44.4);
+
+        let (pitch, offset) = tuning.nearest(tuning.frequency(self::pitch("F#2")));
+        assert!(pitch.is_enharmonic_to(self::pitch("F#2")));
+        assert_close(offset, 0.0);
+
+        assert_close(cents(1.5), 701.96);
+        assert_close(ratio(1200.0), 2.0);
+    }
+}
@@ -0,0 +1,613 @@
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fmt, fs, io};
+
+use crate::pitch::Pitch;
+use crate::tuning::{self, Tuning};
+
+#[derive(fmt::Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseScalaErrorKind {
+    /// The file ended before all of its fields were read.
+    UnexpectedEnd,
+    InvalidCount,
+    /// A scale pitch that is neither cents (with a decimal point) nor a
+    /// positive ratio.
+    InvalidPitch,
+    InvalidNumber,
+    /// A keyboard mapping entry that is neither a scale degree nor `x`.
+    InvalidDegree,
+    /// More scale pitches or mapping entries than the file's count.
+    TrailingLines,
+    /// The keyboard mapping gives a frequency for a key it leaves unmapped.
+    UnmappedReference,
+    /// The keyboard mapping's last key is below its first.
+    InvalidKeyRange,
+}
+
+/// Why a Scala file couldn't be parsed, and the line (counting from 1) at
+/// which the problem was found.
+#[derive(fmt::Debug, Clone, Eq, PartialEq)]
+pub struct ParseScalaError {
+    pub kind: ParseScalaErrorKind,
+    pub line: usize,
+}
+
+impl fmt::Display for ParseScalaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ParseScalaErrorKind::*;
+
+        let description = match self.kind {
+            UnexpectedEnd => "unexpected end of file",
+            InvalidCount => "invalid note count",
+            InvalidPitch => "invalid pitch",
+            InvalidNumber => "invalid number",
+            InvalidDegree => "invalid scale degree",
+            TrailingLines => "more entries than declared",
+            UnmappedReference => "reference note is unmapped",
+            InvalidKeyRange => "last key is below the first",
+        };
+        write!(f, "{} at line {}", description, self.line)
+    }
+}
+
+/// Why a Scala file couldn't be loaded.
+#[derive(fmt::Debug)]
+pub enum LoadScalaError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, ParseScalaError),
+}
+
+impl fmt::Display for LoadScalaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadScalaError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            LoadScalaError::Parse(path, err) => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for LoadScalaError {}
+
+fn load<T: FromStr<Err = ParseScalaError>>(path: &Path) -> Result<T, LoadScalaError> {
+    let text =
+        fs::read_to_string(path).map_err(|err| LoadScalaError::Io(path.to_path_buf(), err))?;
+    text.parse()
+        .map_err(|err| LoadScalaError::Parse(path.to_path_buf(), err))
+}
+
+/// A pitch in a Scala scale, above the scale's implicit 1/1.
+#[derive(fmt::Debug, Copy, Clone, PartialEq)]
+pub enum ScalaPitch {
+    Cents(f64),
+    Ratio(u64, u64),
+}
+
+impl ScalaPitch {
+    pub fn cents(self) -> f64 {
+        match self {
+            ScalaPitch::Cents(cents) => cents,
+            ScalaPitch::Ratio(numerator, denominator) => {
+                tuning::cents(numerator as f64 / denominator as f64)
+            }
+        }
+    }
+}
+
+impl FromStr for ScalaPitch {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('.') {
+            return s.parse().map(ScalaPitch::Cents).map_err(|_| ());
+        }
+
+        let (numerator, denominator) = match s.find('/') {
+            Some(slash) => (&s[..slash], &s[slash + 1..]),
+            None => (s, "1"),
+        };
+        let numerator: u64 = numerator.parse().map_err(|_| ())?;
+        let denominator: u64 = denominator.parse().map_err(|_| ())?;
+        if numerator == 0 || denominator == 0 {
+            return Err(());
+        }
+        Ok(ScalaPitch::Ratio(numerator, denominator))
+    }
+}
+
+// The lines of a Scala file that aren't comments, with their line numbers.
+// Everything after the first word of a line is ignored, except for the
+// description line, which is returned whole.
+struct Lines<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    last: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(s: &'a str) -> Self {
+        Lines {
+            lines: s.lines().enumerate(),
+            last: 0,
+        }
+    }
+
+    fn next_line(&mut self, skip_blank: bool) -> Option<(usize, &'a str)> {
+        for (index, line) in &mut self.lines {
+            self.last = index + 1;
+            if line.starts_with('!') || skip_blank && line.trim().is_empty() {
+                continue;
+            }
+            return Some((index + 1, line));
+        }
+        None
+    }
+
+    fn description(&mut self) -> Result<String, ParseScalaError> {
+        self.next_line(false)
+            .map(|(_, line)| line.trim().to_string())
+            .ok_or_else(|| self.error(ParseScalaErrorKind::UnexpectedEnd))
+    }
+
+    fn word(&mut self) -> Result<(usize, &'a str), ParseScalaError> {
+        self.next_line(true)
+            .map(|(number, line)| (number, line.split_whitespace().next().unwrap()))
+            .ok_or_else(|| self.error(ParseScalaErrorKind::UnexpectedEnd))
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<(usize, T), ParseScalaError> {
+        let (line, word) = self.word()?;
+        word.parse()
+            .map(|n| (line, n))
+            .map_err(|_| ParseScalaError {
+                kind: ParseScalaErrorKind::InvalidNumber,
+                line,
+            })
+    }
+
+    fn end(&mut self) -> Result<(), ParseScalaError> {
+        match self.next_line(true) {
+            Some((line, _)) => Err(ParseScalaError {
+                kind: ParseScalaErrorKind::TrailingLines,
+                line,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn error(&self, kind: ParseScalaErrorKind) -> ParseScalaError {
+        ParseScalaError {
+            kind,
+            line: self.last,
+        }
+    }
+}
+
+/// A scale read from a Scala `.scl` file: the pitches above an implicit 1/1,
+/// the last of which is the interval the scale repeats at (usually 2/1).
+#[derive(fmt::Debug, Clone, PartialEq)]
+pub struct ScalaScale {
+    pub description: String,
+    pub pitches: Vec<ScalaPitch>,
+}
+
+impl ScalaScale {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadScalaError> {
+        load(path.as_ref())
+    }
+
+    /// The number of notes in each period, counting the 1/1.
+    pub fn len(&self) -> usize {
+        self.pitches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pitches.is_empty()
+    }
+
+    pub fn period(&self) -> f64 {
+        self.pitches.last().unwrap().cents()
+    }
+
+    /// The pitch of scale degree `degree` in cents above the 1/1, repeating
+    /// the scale by its period in both directions.
+    pub fn degree_cents(&self, degree: i32) -> f64 {
+        let len = self.len() as i32;
+        let step = degree.rem_euclid(len) as usize;
+        let within = if step == 0 {
+            0.0
+        } else {
+            self.pitches[step - 1].cents()
+        };
+        degree.div_euclid(len) as f64 * self.period() + within
+    }
+}
+
+impl FromStr for ScalaScale {
+    type Err = ParseScalaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Lines::new(s);
+        let description = lines.description()?;
+
+        let (line, count) = lines.word()?;
+        let count = match count.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                return Err(ParseScalaError {
+                    kind: ParseScalaErrorKind::InvalidCount,
+                    line,
+                })
+            }
+        };
+
+        let mut pitches = Vec::new();
+        for _ in 0..count {
+            let (line, word) = lines.word()?;
+            let pitch = word.parse().map_err(|_| ParseScalaError {
+                kind: ParseScalaErrorKind::InvalidPitch,
+                line,
+            })?;
+            pitches.push(pitch);
+        }
+        lines.end()?;
+
+        Ok(ScalaScale {
+            description,
+            pitches,
+        })
+    }
+}
+
+/// How MIDI keys map to the degrees of a scale, read from a Scala `.kbm`
+/// file. Keys are numbered as by `Pitch::semitones`, with middle C as 60.
+#[derive(fmt::Debug, Clone, PartialEq)]
+pub struct KeyboardMapping {
+    pub first_key: i16,
+    pub last_key: i16,
+    /// The key that sounds the scale's 1/1.
+    pub middle_key: i16,
+    pub reference_key: i16,
+    pub reference_frequency: f64,
+    /// The scale degree that each repetition of the mapping moves up by.
+    pub octave_degree: i32,
+    /// How many keys the mapping spans before it repeats. If 0, consecutive
+    /// keys play consecutive degrees.
+    pub size: usize,
+    /// The scale degree of each key from `middle_key` upwards; an unmapped
+    /// key is silent, as are keys past the end up to `size`.
+    pub mapping: Vec<Option<i32>>,
+}
+
+impl KeyboardMapping {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadScalaError> {
+        load(path.as_ref())
+    }
+
+    /// Consecutive keys over the full MIDI range play consecutive degrees,
+    /// with the 1/1 on `middle_key`.
+    pub fn linear(middle_key: i16, reference_key: i16, reference_frequency: f64) -> Self {
+        KeyboardMapping {
+            first_key: 0,
+            last_key: 127,
+            middle_key,
+            reference_key,
+            reference_frequency,
+            octave_degree: 0,
+            size: 0,
+            mapping: Vec::new(),
+        }
+    }
+
+    /// The scale degree played by `key` and how many repetitions of the
+    /// mapping it lies above `middle_key`, ignoring the retuned key range.
+    pub fn degree(&self, key: i16) -> Option<(i32, i32)> {
+        let offset = key as i32 - self.middle_key as i32;
+        if self.size == 0 {
+            return Some((offset, 0));
+        }
+
+        let size = i32::try_from(self.size).unwrap_or(i32::MAX);
+        let degree = *self.mapping.get(offset.rem_euclid(size) as usize)?;
+        degree.map(|degree| (degree, offset.div_euclid(size)))
+    }
+}
+
+/// The mapping Scala uses when none is given: a linear mapping with the 1/1
+/// on middle C, tuned as in equal temperament with A4 at 440 Hz.
+impl Default for KeyboardMapping {
+    fn default() -> Self {
+        KeyboardMapping::linear(60, 60, 440.0 * tuning::ratio(-900.0))
+    }
+}
+
+impl FromStr for KeyboardMapping {
+    type Err = ParseScalaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Lines::new(s);
+        let (_, size) = lines.number::<usize>()?;
+        let (_, first_key) = lines.number()?;
+        let (last_line, last_key) = lines.number()?;
+        if last_key < first_key {
+            return Err(ParseScalaError {
+                kind: ParseScalaErrorKind::InvalidKeyRange,
+                line: last_line,
+            });
+        }
+        let (_, middle_key) = lines.number()?;
+        let (reference_line, reference_key) = lines.number()?;
+        let (_, reference_frequency) = lines.number()?;
+        let (_, octave_degree) = lines.number()?;
+
+        // Unmapped keys at the end of the pattern may be left out.
+        let mut mapping = Vec::new();
+        while mapping.len() < size {
+            let (line, word) = match lines.next_line(true) {
+                Some((line, text)) => (line, text.split_whitespace().next().unwrap()),
+                None => break,
+            };
+            mapping.push(match word {
+                "x" | "X" => None,
+                _ => Some(word.parse().map_err(|_| ParseScalaError {
+                    kind: ParseScalaErrorKind::InvalidDegree,
+                    line,
+                })?),
+            });
+        }
+        lines.end()?;
+
+        let result = KeyboardMapping {
+            first_key,
+            last_key,
+            middle_key,
+            reference_key,
+            reference_frequency,
+            octave_degree,
+            size,
+            mapping,
+        };
+        if result.degree(reference_key).is_none() {
+            return Err(ParseScalaError {
+                kind: ParseScalaErrorKind::UnmappedReference,
+                line: reference_line,
+            });
+        }
+        Ok(result)
+    }
+}
+
+/// A tuning loaded from Scala files. Pitches sound at the frequency of the
+/// MIDI key given by `Pitch::semitones`, so enharmonic spellings sound the
+/// same, and pitches on unmapped keys or outside the mapping's key range
+/// have no frequency.
+#[derive(fmt::Debug, Clone, PartialEq)]
+pub struct ScalaTuning {
+    pub scale: ScalaScale,
+    pub mapping: KeyboardMapping,
+}
+
+impl ScalaTuning {
+    pub fn new(scale: ScalaScale, mapping: KeyboardMapping) -> Self {
+        ScalaTuning { scale, mapping }
+    }
+
+    /// The frequency of MIDI key `key` in hertz, if it is mapped.
+    pub fn key_frequency(&self, key: i16) -> Option<f64> {
+        if key < self.mapping.first_key || key > self.mapping.last_key {
+            return None;
+        }
+
+        let cents = self.key_cents(key)?;
+        let reference = self.key_cents(self.mapping.reference_key)?;
+        Some(self.mapping.reference_frequency * tuning::ratio(cents - reference))
+    }
+
+    // In cents above the key that plays the 1/1.
+    fn key_cents(&self, key: i16) -> Option<f64> {
+        let (degree, repetitions) = self.mapping.degree(key)?;
+        Some(
+            self.scale.degree_cents(degree)
+                + repetitions as f64 * self.scale.degree_cents(self.mapping.octave_degree),
+        )
+    }
+}
+
+impl From<ScalaScale> for ScalaTuning {
+    fn from(scale: ScalaScale) -> Self {
+        ScalaTuning::new(scale, KeyboardMapping::default())
+    }
+}
+
+impl Tuning for ScalaTuning {
+    /// The frequency of `pitch`, or NaN if its key is unmapped.
+    fn frequency(&self, pitch: Pitch) -> f64 {
+        self.key_frequency(pitch.semitones()).unwrap_or(f64::NAN)
+    }
+
+    /// The pitch on the mapped key that sounds closest to `frequency`, spelled
+    /// as `Pitch::from_semitones` would. If no key is mapped, that's the
+    /// reference key, with an offset of NaN.
+    fn nearest(&self, frequency: f64) -> (Pitch, f64) {
+        (self.mapping.first_key..=self.mapping.last_key)
+            .filter_map(|key| {
+                let offset = tuning::cents(frequency / self.key_frequency(key)?);
+                Some((Pitch::from_semitones(key), offset))
+            })
+            .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+            .unwrap_or((Pitch::from_semitones(self.mapping.reference_key), f64::NAN))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::tests::{assert_close, pitch};
+    use crate::tuning::EqualTemperament;
+
+    const MEANQUAR_SCL: &str = include_str!("../../data/scala/meanquar.scl");
+    const PYTH_12_SCL: &str = include_str!("../../data/scala/pyth_12.scl");
+    const SLENDRO_SCL: &str = include_str!("../../data/scala/slendro.scl");
+    const EXAMPLE_KBM: &str = include_str!("../../data/scala/example.kbm");
+    const WHITE_KEYS_KBM: &str = include_str!("../../data/scala/white_keys.kbm");
+
+    fn data(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("data/scala")
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_scale() {
+        let meantone: ScalaScale = MEANQUAR_SCL.parse().unwrap();
+        assert_eq!(
+            meantone.description,
+            "1/4-comma meantone scale. Pietro Aaron's temperament (1523)"
+        );
+        assert_eq!(meantone.len(), 12);
+        assert_eq!(meantone.pitches[0], ScalaPitch::Cents(76.049));
+        assert_eq!(meantone.pitches[3], ScalaPitch::Ratio(5, 4));
+        assert_close(meantone.degree_cents(4), 386.31);
+        assert_close(meantone.degree_cents(12), 1200.0);
+        assert_close(meantone.degree_cents(-1), -117.11);
+
+        let pythagorean = ScalaScale::load(data("pyth_12.scl")).unwrap();
+        assert_eq!(pythagorean, PYTH_12_SCL.parse().unwrap());
+        assert_eq!(pythagorean.pitches[0], ScalaPitch::Ratio(2187, 2048));
+        assert_close(pythagorean.degree_cents(7), 701.96);
+
+        let slendro: ScalaScale = SLENDRO_SCL.parse().unwrap();
+        assert_eq!(slendro.len(), 5);
+        assert_close(slendro.period(), 1200.0);
+
+        let tiny: ScalaScale = "\n 2\n 3/2 fifth\n 2\n".parse().unwrap();
+        assert_eq!(tiny.description, "");
+        assert_eq!(
+            tiny.pitches,
+            [ScalaPitch::Ratio(3, 2), ScalaPitch::Ratio(2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseScalaErrorKind::*;
+
+        let error = |s: &str| s.parse::<ScalaScale>().unwrap_err();
+        assert_eq!(error("").kind, UnexpectedEnd);
+        assert_eq!(
+            error("! comment\nname\n3\n100.0\n"),
+            ParseScalaError {
+                kind: UnexpectedEnd,
+                line: 4,
+            }
+        );
+        assert_eq!(error("name\nthree\n").kind, InvalidCount);
+        assert_eq!(error("name\n0\n").kind, InvalidCount);
+        assert_eq!(
+            error("name\n2\n3/2\n2/0\n"),
+            ParseScalaError {
+                kind: InvalidPitch,
+                line: 4,
+            }
+        );
+        assert_eq!(error("name\n1\n-3/2\n").kind, InvalidPitch);
+        assert_eq!(error("name\n1\n2/1\n3/1\n").kind, TrailingLines);
+        assert_eq!(
+            error(&format!("name\n{}\n2/1\n", usize::MAX)).kind,
+            UnexpectedEnd
+        );
+        assert_eq!(
+            error("name\n1\nfoo\n").to_string(),
+            "invalid pitch at line 3"
+        );
+
+        let error = |s: &str| s.parse::<KeyboardMapping>().unwrap_err();
+        assert_eq!(error("12\n0\n127\n60\n69\n440.0\n").kind, UnexpectedEnd);
+        assert_eq!(error("12\n0\n127\n60\n69\nA4\n12\n").kind, InvalidNumber);
+        assert_eq!(
+            error("2\n0\n127\n60\n60\n440.0\n12\n0\ny\n"),
+            ParseScalaError {
+                kind: InvalidDegree,
+                line: 9,
+            }
+        );
+        assert_eq!(
+            error("2\n0\n127\n60\n61\n440.0\n12\n0\nx\n"),
+            ParseScalaError {
+                kind: UnmappedReference,
+                line: 5,
+            }
+        );
+        assert_eq!(
+            error(&format!("{}\n0\n127\n60\n60\n440.0\n12\n", usize::MAX)),
+            ParseScalaError {
+                kind: UnmappedReference,
+                line: 5,
+            }
+        );
+        assert_eq!(
+            error("0\n10\n5\n60\n69\n440.0\n12\n"),
+            ParseScalaError {
+                kind: InvalidKeyRange,
+                line: 3,
+            }
+        );
+
+        match ScalaScale::load(data("missing.scl")) {
+            Err(LoadScalaError::Io(path, _)) => assert_eq!(path, data("missing.scl")),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_scala_tuning() {
+        let mapping: KeyboardMapping = EXAMPLE_KBM.parse().unwrap();
+        assert_eq!(mapping.mapping.len(), 12);
+        let meantone = ScalaTuning::new(MEANQUAR_SCL.parse().unwrap(), mapping);
+        assert_close(meantone.frequency(pitch("A4")), 440.0);
+        assert_close(meantone.frequency(pitch("C#5")), 550.0);
+        assert_close(meantone.frequency(pitch("Db5")), 550.0);
+        assert_close(meantone.frequency(pitch("A3")), 220.0);
+        assert_close(meantone.frequency(pitch("C4")), 263.18);
+
+        let (nearest, offset) = meantone.nearest(551.0);
+        assert_eq!(nearest, pitch("C#5"));
+        assert_close(offset, tuning::cents(551.0 / 550.0));
+
+        let pythagorean = ScalaTuning::from(PYTH_12_SCL.parse::<ScalaScale>().unwrap());
+        let equal = EqualTemperament::default();
+        assert_close(
+            pythagorean.frequency(pitch("C4")),
+            equal.frequency(pitch("C4")),
+        );
+        assert_close(pythagorean.frequency(pitch("G2")), 261.626 * 3.0 / 8.0);
+        assert!(pythagorean.key_frequency(128).is_none());
+
+        // A pentatonic scale on the white keys, leaving the black keys silent.
+        let mapping = KeyboardMapping::load(data("white_keys.kbm")).unwrap();
+        assert_eq!(mapping, WHITE_KEYS_KBM.parse().unwrap());
+        assert_eq!((mapping.size, mapping.mapping.len()), (12, 10));
+        let slendro = ScalaTuning::new(SLENDRO_SCL.parse().unwrap(), mapping);
+        assert_close(slendro.frequency(pitch("C4")), 261.63);
+        assert_close(
+            slendro.frequency(pitch("D4")),
+            261.626 * tuning::ratio(231.0),
+        );
+        assert_close(slendro.frequency(pitch("C5")), 523.25);
+        assert_close(
+            slendro.frequency(pitch("A3")),
+            261.626 * tuning::ratio(-240.0),
+        );
+        assert!(slendro.frequency(pitch("C#4")).is_nan());
+        assert_eq!(slendro.nearest(300.0).0, pitch("D4"));
+
+        // The fields are public, so a mapping may have no keys at all.
+        let mapping = KeyboardMapping {
+            first_key: 10,
+            last_key: 5,
+            ..KeyboardMapping::default()
+        };
+        let (nearest, offset) = ScalaTuning::new(slendro.scale, mapping).nearest(300.0);
+        assert_eq!(nearest, pitch("C4"));
+        assert!(offset.is_nan());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::interval::Interval;
+use crate::key;
+use crate::note::Note;
+use crate::pitch::Pitch;
+use crate::tuning::{self, EqualTemperament, Tuning, A4_SEMITONES, CONCERT_A};
+
+/// A temperament built from a chain of equal fifths, with A4 at `reference`
+/// hertz. Notes are tuned by their spelling, so that in meantone G# is lower
+/// than Ab.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RegularTemperament {
+    /// The size of the fifth in cents.
+    pub fifth: f64,
+    pub reference: f64,
+}
+
+impl RegularTemperament {
+    /// Pure 3:2 fifths.
+    pub fn pythagorean(reference: f64) -> Self {
+        RegularTemperament {
+            fifth: tuning::cents(3.0 / 2.0),
+            reference,
+        }
+    }
+
+    /// Fifths narrowed by a quarter of a syntonic comma, which makes the
+    /// major thirds pure 5:4.
+    pub fn quarter_comma_meantone(reference: f64) -> Self {
+        RegularTemperament {
+            fifth: tuning::cents(5f64.powf(0.25)),
+            reference,
+        }
+    }
+}
+
+impl Tuning for RegularTemperament {
+    fn frequency(&self, pitch: Pitch) -> f64 {
+        let a4 = Pitch::from_semitones(A4_SEMITONES);
+        let fifths = key::note_fifths(pitch.note()) - key::note_fifths(a4.note());
+        // Each fifth moves four letters up; the octaves make up the rest.
+        let steps = pitch.diatonic_steps() - a4.diatonic_steps();
+        let octaves = (steps - 4 * fifths) / 7;
+
+        self.reference * tuning::ratio(fifths as f64 * self.fifth + octaves as f64 * 1200.0)
+    }
+}
+
+/// Five-limit just intonation: each note is tuned by a ratio of small whole
+/// numbers above `tonic`, chosen by the interval it's spelled at. The tonic
+/// itself sounds as in equal temperament with A4 at `reference` hertz.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct JustIntonation {
+    pub tonic: Note,
+    pub reference: f64,
+}
+
+impl JustIntonation {
+    pub const fn new(tonic: Note, reference: f64) -> Self {
+        JustIntonation { tonic, reference }
+    }
+}
+
+// Ratios by interval number and size in semitones.
+const JUST_RATIOS: [(u8, i16, f64); 20] = [
+    (1, 0, 1.0),
+    (1, 1, 25.0 / 24.0),
+    (2, 1, 16.0 / 15.0),
+    (2, 2, 9.0 / 8.0),
+    (2, 3, 75.0 / 64.0),
+    (3, 3, 6.0 / 5.0),
+    (3, 4, 5.0 / 4.0),
+    (4, 4, 32.0 / 25.0),
+    (4, 5, 4.0 / 3.0),
+    (4, 6, 45.0 / 32.0),
+    (5, 6, 64.0 / 45.0),
+    (5, 7, 3.0 / 2.0),
+    (5, 8, 25.0 / 16.0),
+    (6, 8, 8.0 / 5.0),
+    (6, 9, 5.0 / 3.0),
+    (7, 9, 128.0 / 75.0),
+    (7, 10, 9.0 / 5.0),
+    (7, 11, 15.0 / 8.0),
+    (7, 12, 125.0 / 64.0),
+    (8, 11, 48.0 / 25.0),
+];
+
+// For intervals missing from the table, the ratio of the usual interval of
+// the same size.
+const JUST_SEMITONES: [f64; 12] = [
+    1.0,
+    16.0 / 15.0,
+    9.0 / 8.0,
+    6.0 / 5.0,
+    5.0 / 4.0,
+    4.0 / 3.0,
+    45.0 / 32.0,
+    3.0 / 2.0,
+    8.0 / 5.0,
+    5.0 / 3.0,
+    9.0 / 5.0,
+    15.0 / 8.0,
+];
+
+impl Tuning for JustIntonation {
+    fn frequency(&self, pitch: Pitch) -> f64 {
+        let interval = Interval::between(self.tonic, pitch.note());
+        let semitones = interval.semitones();
+        let ratio = JUST_RATIOS
+            .iter()
+            .find(|&&(number, size, _)| number == interval.number() && size == semitones)
+            .map_or_else(
+                || {
+                    JUST_SEMITONES[semitones.rem_euclid(12) as usize]
+                        * 2f64.powi(semitones.div_euclid(12) as i32)
+                },
+                |&(_, _, ratio)| ratio,
+            );
+
+        let tonic = EqualTemperament::new(self.reference)
+            .frequency_of_semitones(pitch.semitones() - semitones);
+        tonic * ratio
+    }
+}
+
+/// A fixed tuning of the twelve pitch classes, given in cents above C, with
+/// A4 at `reference` hertz. Enharmonic notes sound the same.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WellTemperament {
+    pub cents: [f64; 12],
+    pub reference: f64,
+}
+
+impl WellTemperament {
+    /// Werckmeister's third temperament, which narrows the fifths C-G, G-D,
+    /// D-A and B-F# by a quarter of a Pythagorean comma each.
+    pub fn werckmeister_iii(reference: f64) -> Self {
+        WellTemperament {
+            cents: [
+                0.0, 90.225, 192.18, 294.135, 390.225, 498.045, 588.27, 696.09, 792.18, 888.27,
+                996.09, 1092.18,
+            ],
+            reference,
+        }
+    }
+
+    fn cents_above_c_minus_1(&self, semitones: i16) -> f64 {
+        semitones.div_euclid(12) as f64 * 1200.0 + self.cents[semitones.rem_euclid(12) as usize]
+    }
+}
+
+impl Default for WellTemperament {
+    fn default() -> Self {
+        WellTemperament::werckmeister_iii(CONCERT_A)
+    }
+}
+
+impl Tuning for WellTemperament {
+    fn frequency(&self, pitch: Pitch) -> f64 {
+        let cents = self.cents_above_c_minus_1(pitch.semitones())
+            - self.cents_above_c_minus_1(A4_SEMITONES);
+        self.reference * tuning::ratio(cents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::tests::{assert_close, pitch};
+
+    fn interval(tuning: &impl Tuning, low: &str, high: &str) -> f64 {
+        tuning::cents(tuning.frequency(pitch(high)) / tuning.frequency(pitch(low)))
+    }
+
+    #[test]
+    fn test_regular_temperaments() {
+        let pythagorean = RegularTemperament::pythagorean(CONCERT_A);
+        assert_close(pythagorean.frequency(pitch("A4")), 440.0);
+        assert_close(pythagorean.frequency(pitch("E5")), 660.0);
+        assert_close(pythagorean.frequency(pitch("C#5")), 556.88);
+        assert_close(interval(&pythagorean, "C4", "E4"), 407.82);
+        // The Pythagorean comma: twelve fifths overshoot seven octaves.
+        assert_close(interval(&pythagorean, "Ab4", "G#4"), 23.46);
+
+        let meantone = RegularTemperament::quarter_comma_meantone(CONCERT_A);
+        assert_close(meantone.frequency(pitch("C#5")), 550.0);
+        assert_close(interval(&meantone, "C4", "E4"), 386.31);
+        assert_close(interval(&meantone, "C4", "G4"), 696.58);
+        assert_close(interval(&meantone, "G#4", "Ab4"), 41.06);
+
+        let (nearest, offset) = meantone.nearest(meantone.frequency(pitch("Db5")));
+        assert_eq!(nearest, pitch("Db5"));
+        assert_close(offset, 0.0);
+
+        let equal = RegularTemperament {
+            fifth: 700.0,
+            reference: CONCERT_A,
+        };
+        assert_close(
+            equal.frequency(pitch("Cb3")),
+            EqualTemperament::default().frequency(pitch("B2")),
+        );
+    }
+
+    #[test]
+    fn test_just_intonation() {
+        let just = JustIntonation::new("C".parse().unwrap(), CONCERT_A);
+        assert_close(just.frequency(pitch("C4")), 261.63);
+        assert_close(just.frequency(pitch("E4")), 261.626 * 5.0 / 4.0);
+        assert_close(just.frequency(pitch("A4")), 261.626 * 5.0 / 3.0);
+        assert_close(just.frequency(pitch("G2")), 261.626 * 3.0 / 8.0);
+        assert_close(interval(&just, "C4", "Eb4"), tuning::cents(6.0 / 5.0));
+        assert_close(interval(&just, "C4", "F#4"), tuning::cents(45.0 / 32.0));
+        assert_close(interval(&just, "C4", "Gb4"), tuning::cents(64.0 / 45.0));
+        assert_close(interval(&just, "C4", "B#4"), tuning::cents(125.0 / 64.0));
+        assert_close(
+            interval(&just, "C4", "Cb4"),
+            tuning::cents(48.0 / 25.0) - 1200.0,
+        );
+    }
+
+    #[test]
+    fn test_werckmeister() {
+        let werckmeister = WellTemperament::default();
+        assert_close(werckmeister.frequency(pitch("A4")), 440.0);
+        assert_close(interval(&werckmeister, "C4", "E4"), 390.23);
+        assert_close(interval(&werckmeister, "C4", "G4"), 696.09);
+        assert_close(interval(&werckmeister, "F#4", "C#5"), 701.96);
+        assert_close(interval(&werckmeister, "A3", "A4"), 1200.0);
+        assert_eq!(
+            werckmeister.frequency(pitch("G#4")),
+            werckmeister.frequency(pitch("Ab4"))
+        );
+    }
+}